@@ -0,0 +1,296 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path, str::FromStr};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till},
+    character::complete::{self, line_ending, not_line_ending, space0, space1},
+    combinator::{all_consuming, map, opt, value},
+    error::Error,
+    multi::separated_list0,
+    sequence::{delimited, preceded, separated_pair, terminated},
+    Finish, IResult,
+};
+
+use crate::{
+    error::{AocError, Result},
+    input::ASSETS_DIR,
+    solution::{parse_name, Answer, Part},
+};
+
+/// Name of the file storing the known answers, next to the puzzle inputs.
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// Something we know about the answer of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expectation {
+    /// `day01a = 70374`: the answer was accepted.
+    Equal(Answer),
+    /// `day17a != 3100`: the answer was rejected.
+    NotEqual(Answer),
+    /// `day17a < 3266`: the answer was rejected for being too high.
+    LessThan(i64),
+    /// `day22a > 33242`: the answer was rejected for being too low.
+    GreaterThan(i64),
+}
+
+impl Expectation {
+    pub fn parse(i: &str) -> IResult<&str, Expectation> {
+        let answer = |i| map(take_till(|c| c == '\r' || c == '\n'), str::trim)(i);
+        alt((
+            map(preceded(tag("!="), answer), |a| {
+                Expectation::NotEqual(Answer::from_inline(a))
+            }),
+            map(preceded(tag("="), answer), |a| {
+                Expectation::Equal(Answer::from_inline(a))
+            }),
+            map(preceded(tag("<"), preceded(space0, complete::i64)), |a| {
+                Expectation::LessThan(a)
+            }),
+            map(preceded(tag(">"), preceded(space0, complete::i64)), |a| {
+                Expectation::GreaterThan(a)
+            }),
+        ))(i)
+    }
+
+    /// Whether the answer is compatible with this expectation, answers of different types
+    /// never being equal and only integers being bounded.
+    pub fn accepts(&self, answer: &Answer) -> bool {
+        match self {
+            Expectation::Equal(expected) => answer == expected,
+            Expectation::NotEqual(rejected) => answer != rejected,
+            Expectation::LessThan(bound) => answer.as_int().is_some_and(|a| a < *bound as i128),
+            Expectation::GreaterThan(bound) => answer.as_int().is_some_and(|a| a > *bound as i128),
+        }
+    }
+}
+
+impl Display for Expectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expectation::Equal(a) => write!(f, "= {}", a.inline()),
+            Expectation::NotEqual(a) => write!(f, "!= {}", a.inline()),
+            Expectation::LessThan(a) => write!(f, "< {a}"),
+            Expectation::GreaterThan(a) => write!(f, "> {a}"),
+        }
+    }
+}
+
+/// The outcome of checking an answer against the known answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// The answer is wrong, or could not be computed, for the given reason.
+    Fail(String),
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(_) => write!(f, "FAIL"),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// The answers we know about, keyed by day and part.
+///
+/// They are stored one per line, lines starting with `#` being comments:
+///
+/// ```text
+/// # 3266 is too high
+/// day17a < 3266
+/// day01a = 70374
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownAnswers(BTreeMap<(u8, Part), Vec<Expectation>>);
+
+impl KnownAnswers {
+    pub fn parse(i: &str) -> IResult<&str, KnownAnswers> {
+        let comment = value(None, preceded(tag("#"), not_line_ending));
+        let blank = value(None, space0);
+        let entry = map(separated_pair(parse_name, space1, Expectation::parse), Some);
+        let (i, lines) = terminated(
+            separated_list0(
+                line_ending,
+                delimited(space0, alt((entry, comment, blank)), space0),
+            ),
+            opt(line_ending),
+        )(i)?;
+
+        let mut answers = KnownAnswers::default();
+        for ((day, part), expectation) in lines.into_iter().flatten() {
+            answers.add(day, part, expectation);
+        }
+        Ok((i, answers))
+    }
+
+    /// Load the answers from a file, a missing file meaning no known answers.
+    pub fn load<P>(path: P) -> Result<KnownAnswers>
+    where
+        P: AsRef<Path>,
+    {
+        match fs::read_to_string(path) {
+            Ok(data) => all_consuming(KnownAnswers::parse)(&data)
+                .finish()
+                .map(|(_, answers)| answers)
+                .map_err(|e| AocError::from_nom(&data, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(KnownAnswers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Load the answers stored next to the puzzle inputs.
+    pub fn load_default() -> Result<KnownAnswers> {
+        KnownAnswers::load(Path::new(ASSETS_DIR).join(ANSWERS_FILE))
+    }
+
+    pub fn add(&mut self, day: u8, part: Part, expectation: Expectation) {
+        self.0.entry((day, part)).or_default().push(expectation);
+    }
+
+    /// The puzzles we know something about, ordered by day and part.
+    pub fn puzzles(&self) -> impl Iterator<Item = (u8, Part)> + '_ {
+        self.0.keys().copied()
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> &[Expectation] {
+        self.0.get(&(day, part)).map_or(&[], |e| e.as_slice())
+    }
+
+    /// Check an answer: it passes when it is the accepted one, fails when it contradicts
+    /// anything we know, and is unknown otherwise.
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+        let expectations = self.get(day, part);
+        if let Some(failed) = expectations.iter().find(|e| !e.accepts(answer)) {
+            return Verdict::Fail(format!("expected {failed}"));
+        }
+        if expectations
+            .iter()
+            .any(|e| matches!(e, Expectation::Equal(_)))
+        {
+            Verdict::Pass
+        } else {
+            Verdict::Unknown
+        }
+    }
+}
+
+impl FromStr for KnownAnswers {
+    type Err = Error<String>;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match all_consuming(KnownAnswers::parse)(s).finish() {
+            Ok((_, item)) => Ok(item),
+            Err(Error { input, code }) => Err(Error {
+                input: input.to_string(),
+                code,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RAW_INPUT: &str = "# Confirmed answers.
+day01a = 24000
+  day05a = CMZ
+
+# 3266 is too high
+day17a < 3266
+day17a != 3100
+day22a > 33242
+";
+
+    #[test]
+    fn test_parse_known_answers() {
+        let answers = RAW_INPUT.parse::<KnownAnswers>().unwrap();
+        assert_eq!(
+            answers.get(1, Part::A),
+            &[Expectation::Equal(Answer::Int(24000))]
+        );
+        assert_eq!(
+            answers.get(5, Part::A),
+            &[Expectation::Equal(Answer::from("CMZ"))]
+        );
+        assert_eq!(
+            answers.get(17, Part::A),
+            &[
+                Expectation::LessThan(3266),
+                Expectation::NotEqual(Answer::Int(3100))
+            ]
+        );
+        assert_eq!(answers.get(22, Part::A), &[Expectation::GreaterThan(33242)]);
+        assert_eq!(answers.get(22, Part::B), &[]);
+        assert!("day01a ~ 12".parse::<KnownAnswers>().is_err());
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let path = std::env::temp_dir().join(format!("aoc2022-answers-{}", std::process::id()));
+        assert_eq!(KnownAnswers::load(&path).unwrap(), KnownAnswers::default());
+        fs::write(&path, "day01a = 24000\nday01b ~ 12\n").unwrap();
+        let error = KnownAnswers::load(&path).unwrap_err();
+        fs::remove_file(path).unwrap();
+        assert!(matches!(error, AocError::Parse { line: 2, .. }));
+        assert_eq!(
+            error.to_string(),
+            "Parse error on line 2: Eof failed on `day01b ~ 12`"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = RAW_INPUT.parse::<KnownAnswers>().unwrap();
+        assert_eq!(
+            answers.check(1, Part::A, &Answer::Int(24000)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(1, Part::A, &Answer::Int(24001)),
+            Verdict::Fail(String::from("expected = 24000"))
+        );
+        assert_eq!(
+            answers.check(17, Part::A, &Answer::Int(3065)),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.check(17, Part::A, &Answer::Int(3266)),
+            Verdict::Fail(String::from("expected < 3266"))
+        );
+        assert_eq!(
+            answers.check(17, Part::A, &Answer::Int(3100)),
+            Verdict::Fail(String::from("expected != 3100"))
+        );
+        assert_eq!(
+            answers.check(22, Part::A, &Answer::Int(33242)),
+            Verdict::Fail(String::from("expected > 33242"))
+        );
+        assert_eq!(
+            answers.check(2, Part::A, &Answer::Int(15)),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn test_check_types() {
+        let answers = "day01a = 24000\nday10b = #./.#\nday17a < 3266\n"
+            .parse::<KnownAnswers>()
+            .unwrap();
+        assert_eq!(
+            answers.check(1, Part::A, &Answer::from("24000")),
+            Verdict::Fail(String::from("expected = 24000"))
+        );
+        assert_eq!(
+            answers.check(10, Part::B, &Answer::picture("#.\n.#\n")),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(17, Part::A, &Answer::from("CMZ")),
+            Verdict::Fail(String::from("expected < 3266"))
+        );
+    }
+}
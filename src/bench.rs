@@ -0,0 +1,152 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::{
+    error::Result,
+    runner::format_duration,
+    solution::{Answer, Solution},
+};
+
+pub const DEFAULT_WARMUP: usize = 3;
+pub const DEFAULT_RUNS: usize = 10;
+
+/// Summary statistics of a set of timings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let count = sorted.len();
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+        let mean = sorted.iter().sum::<Duration>() / count as u32;
+        let variance = sorted
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Some(Stats {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// The timings of a solution processing the same input repeatedly, the input being
+/// parsed once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bench {
+    pub name: String,
+    pub answer: Answer,
+    pub runs: usize,
+    pub parse: Duration,
+    pub solve: Stats,
+}
+
+impl Display for Bench {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{}: {} ({} runs)",
+            self.name,
+            self.answer.inline(),
+            self.runs
+        )?;
+        writeln!(
+            f,
+            "{:<6} {:>10} (once)",
+            "Parse",
+            format_duration(self.parse)
+        )?;
+        writeln!(
+            f,
+            "{:<6} {:>10} {:>10} {:>10} {:>10}",
+            "", "Min", "Median", "Mean", "Stddev"
+        )?;
+        writeln!(
+            f,
+            "{:<6} {:>10} {:>10} {:>10} {:>10}",
+            "Solve",
+            format_duration(self.solve.min),
+            format_duration(self.solve.median),
+            format_duration(self.solve.mean),
+            format_duration(self.solve.stddev)
+        )
+    }
+}
+
+/// Parse the input once, timing it, then process it `warmup` times without recording
+/// anything and `runs` times while collecting the solve timings.
+///
+/// Returns `None` when `runs` is 0, and the first error raised by the solution if any.
+pub fn bench(
+    solution: &dyn Solution,
+    input: &str,
+    warmup: usize,
+    runs: usize,
+) -> Result<Option<Bench>> {
+    let repeated = solution.repeat(input, warmup, runs)?;
+    Ok(repeated.answer.and_then(|answer| {
+        Some(Bench {
+            name: solution.name(),
+            answer,
+            runs,
+            parse: repeated.parse,
+            solve: Stats::from_samples(&repeated.solve)?,
+        })
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{day01::Day01a, day02::Day02a};
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 2, 8, 6]
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Stats::from_samples(&samples),
+            Some(Stats {
+                min: Duration::from_millis(2),
+                median: Duration::from_millis(5),
+                mean: Duration::from_millis(5),
+                // sqrt(5) ms
+                stddev: Duration::from_nanos(2_236_068),
+            })
+        );
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_bench() {
+        let result = bench(&Day01a, "1000\n2000\n\n4000\n", 1, 5)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.name, "day01a");
+        assert_eq!(result.answer, Answer::Int(4000));
+        assert_eq!(result.runs, 5);
+        assert!(result.solve.min <= result.solve.median);
+        assert!(bench(&Day01a, "1000\n", 0, 0).unwrap().is_none());
+        assert!(bench(&Day02a, "A W\n", 0, 1).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    answers::ANSWERS_FILE,
+    day07::Disk,
+    day15::Scan,
+    day17::Tower,
+    error::{AocError, Result},
+    input::{Input, ASSETS_DIR},
+    params::Params,
+    runner::Format,
+};
+
+/// Name of the configuration file.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Settings of the command line tool, read from `aoc.toml`.
+///
+/// ```toml
+/// assets-dir = "inputs"
+/// year = 2022
+/// format = "json"
+/// session-file = "/run/secrets/aoc-session"
+///
+/// [day07]
+/// total = 70000000
+/// needed = 30000000
+///
+/// [day15]
+/// row = 10
+///
+/// [day17]
+/// rocks = 2022
+/// ```
+///
+/// Every setting is optional, the command line arguments taking precedence over them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// Directory of the puzzle inputs and of the known answers, `assets` by default.
+    pub assets_dir: Option<PathBuf>,
+    /// Year of the inputs downloaded by `fetch`.
+    pub year: Option<u16>,
+    pub format: Option<Format>,
+    /// File holding the session token, unless `$AOC_SESSION` is set.
+    pub session_file: Option<PathBuf>,
+    // Overrides of the numbers given in the text of the puzzles, one table per day. They
+    // are not flattened from `Params`, which would let unknown keys through.
+    pub day07: Disk,
+    pub day15: Scan,
+    pub day17: Tower,
+}
+
+impl FromStr for Config {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        toml::from_str(s).map_err(|e| AocError::Config(e.to_string()))
+    }
+}
+
+impl Config {
+    /// Read the configuration from `aoc.toml` in the working directory, or else from the
+    /// [configuration directory](config_dir). There being none means the defaults.
+    pub fn load() -> Result<Self> {
+        let paths = [
+            Some(PathBuf::from(CONFIG_FILE)),
+            config_dir().map(|dir| dir.join(CONFIG_FILE)),
+        ];
+        for path in paths.into_iter().flatten() {
+            match Config::from_file(&path) {
+                Err(AocError::Io(e)) if e.kind() == io::ErrorKind::NotFound => continue,
+                config => return config,
+            }
+        }
+        Ok(Config::default())
+    }
+
+    /// Read the configuration from a file, errors mentioning its path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        fs::read_to_string(path)?.parse().map_err(|e| match e {
+            AocError::Config(message) => AocError::Config(format!("{}: {message}", path.display())),
+            e => e,
+        })
+    }
+
+    /// The numbers of the puzzle texts, the defaults overridden by the day tables.
+    pub fn params(&self) -> Params {
+        Params {
+            day07: self.day07,
+            day15: self.day15,
+            day17: self.day17,
+        }
+    }
+
+    pub fn assets_dir(&self) -> PathBuf {
+        self.assets_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(ASSETS_DIR))
+    }
+
+    /// Where the puzzle inputs are read from, unless told otherwise.
+    pub fn input(&self) -> Input {
+        match &self.assets_dir {
+            Some(dir) => Input::Dir(dir.clone()),
+            None => Input::Default,
+        }
+    }
+
+    /// Path of the known answers, within the assets directory.
+    pub fn answers_file(&self) -> PathBuf {
+        self.assets_dir().join(ANSWERS_FILE)
+    }
+}
+
+/// Directory of the configuration and of the session file, `$XDG_CONFIG_HOME/aoc2022` or
+/// `~/.config/aoc2022`.
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config| config.join("aoc2022"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_config_from_str() {
+        assert_eq!("".parse::<Config>().unwrap(), Config::default());
+
+        let config = r#"
+assets-dir = "inputs"
+format = "json"
+
+[day15]
+row = 10
+
+[day07]
+needed = 1
+"#
+        .parse::<Config>()
+        .unwrap();
+        assert_eq!(config.assets_dir(), PathBuf::from("inputs"));
+        assert_eq!(config.input(), Input::Dir(PathBuf::from("inputs")));
+        assert_eq!(config.answers_file(), PathBuf::from("inputs/answers.txt"));
+        assert_eq!(config.format, Some(Format::Json));
+        assert_eq!(
+            config.params(),
+            Params {
+                day07: Disk {
+                    needed: 1,
+                    ..Disk::default()
+                },
+                day15: Scan { row: 10 },
+                day17: Tower::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_config_errors() {
+        assert!(matches!(
+            "format = \"xml\"".parse::<Config>(),
+            Err(AocError::Config(_))
+        ));
+        assert!(matches!(
+            "[day15]\nrows = 10".parse::<Config>(),
+            Err(AocError::Config(_))
+        ));
+        assert!(matches!(
+            "asets-dir = \"nope\"".parse::<Config>(),
+            Err(AocError::Config(_))
+        ));
+        assert!(matches!(
+            "[day16]\nminutes = 30".parse::<Config>(),
+            Err(AocError::Config(_))
+        ));
+        assert!(matches!(
+            Config::from_file("missing.toml"),
+            Err(AocError::Io(e)) if e.kind() == io::ErrorKind::NotFound
+        ));
+    }
+}
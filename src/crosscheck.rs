@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+use crate::{
+    generate::{generate, Rng},
+    runner::run_data,
+    solution::{Answer, Solution},
+};
+
+/// The answer of an implementation, or the reason it failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    /// The name and variant of the implementation, i.e. `day20a/chris`.
+    pub label: String,
+    pub answer: Result<Answer, String>,
+}
+
+/// The outcomes of several implementations of a puzzle run against the same input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crosscheck {
+    pub outcomes: Vec<Outcome>,
+}
+
+impl Crosscheck {
+    /// Run each implementation against the input, catching their panics.
+    pub fn run(implementations: &[&dyn Solution], data: &str) -> Self {
+        let outcomes = implementations
+            .iter()
+            .map(|solution| Outcome {
+                label: solution.label(),
+                answer: run_data(*solution, data).map(|run| run.answer),
+            })
+            .collect();
+        Crosscheck { outcomes }
+    }
+
+    /// Whether the implementations all give the same answer. A failure is never an
+    /// agreement, the implementations may fail for different reasons.
+    pub fn agrees(&self) -> bool {
+        self.outcomes.iter().all(|o| o.answer.is_ok())
+            && self
+                .outcomes
+                .windows(2)
+                .all(|pair| pair[0].answer == pair[1].answer)
+    }
+}
+
+/// One line per implementation, with its answer or its error.
+impl Display for Crosscheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .outcomes
+            .iter()
+            .map(|o| o.label.len())
+            .max()
+            .unwrap_or_default();
+        for Outcome { label, answer } in &self.outcomes {
+            match answer {
+                Ok(answer) => writeln!(f, "{label:<width$} {}", answer.inline())?,
+                Err(e) => writeln!(f, "{label:<width$} Error: {e}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A generated input the implementations disagree on.
+#[derive(Clone, Debug)]
+pub struct Disagreement {
+    /// The seed generating the input again.
+    pub seed: u64,
+    pub input: String,
+    pub crosscheck: Crosscheck,
+}
+
+/// Cross-check the implementations against an input generated from each seed, stopping
+/// at the first disagreement.
+///
+/// The implementations must share a day we can [generate](generate) inputs for, and
+/// `size` must be valid for it.
+pub fn search<I>(implementations: &[&dyn Solution], size: usize, seeds: I) -> Option<Disagreement>
+where
+    I: IntoIterator<Item = u64>,
+{
+    let day = implementations.first()?.day();
+    seeds.into_iter().find_map(|seed| {
+        let input = generate(day, size, &mut Rng::new(seed))
+            .unwrap_or_else(|| panic!("We cannot generate inputs for day {day}"));
+        let crosscheck = Crosscheck::run(implementations, &input);
+        (!crosscheck.agrees()).then_some(Disagreement {
+            seed,
+            input,
+            crosscheck,
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        day20::{Day20a, Day20aChris},
+        solution::{implementations, Part},
+    };
+
+    crate::solution!(Day20aZero, 20, A, "zero", Ok, |_| Ok(0));
+
+    const RAW_INPUT: &str = "1\n2\n-3\n3\n-2\n0\n4\n";
+
+    #[test]
+    fn test_crosscheck() {
+        let crosscheck = Crosscheck::run(&implementations(20, Part::A), RAW_INPUT);
+        assert!(crosscheck.agrees());
+        assert_eq!(crosscheck.to_string(), "day20a       3\nday20a/chris 3\n");
+
+        let crosscheck = Crosscheck::run(&[&Day20a, &Day20aZero], RAW_INPUT);
+        assert!(!crosscheck.agrees());
+        assert_eq!(crosscheck.to_string(), "day20a      3\nday20a/zero 0\n");
+
+        let crosscheck = Crosscheck::run(&[&Day20a, &Day20aChris], "1\n2\n");
+        assert!(crosscheck.outcomes.iter().all(|o| o.answer.is_err()));
+        assert!(!crosscheck.agrees());
+    }
+
+    #[test]
+    fn test_search() {
+        // When the number count divides 1000, the answer is always 0.
+        assert!(search(&implementations(20, Part::A), 101, 0..5).is_none());
+
+        let disagreement = search(&[&Day20a, &Day20aZero], 101, 3..5).unwrap();
+        assert_eq!(disagreement.seed, 3);
+        assert_eq!(
+            Some(disagreement.input),
+            generate(20, 101, &mut Rng::new(3))
+        );
+    }
+}
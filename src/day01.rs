@@ -1,39 +1,58 @@
-use crate::input_sep_from_string;
-use std::fs;
+use std::{num::ParseIntError, str::FromStr};
 
-pub fn day01a() -> String {
-    let max = process_a(&get_groups());
-    max.to_string()
-}
+use crate::{
+    error::{AocError, Result},
+    input_from_string_strict, solution,
+};
+
+solution!(Day01a, 1, A, get_groups, |groups| process_a(groups));
+solution!(Day01b, 1, B, get_groups, |groups| Ok(process_b(groups)));
+
+/// A line of the input: the calories of an item, or the blank line between two elves.
+struct Line(Option<u32>);
 
-pub fn day01b() -> String {
-    let top_three = process_b(&get_groups());
-    top_three.to_string()
+impl FromStr for Line {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(Line(None));
+        }
+        s.parse::<u32>().map(|calories| Line(Some(calories)))
+    }
 }
 
-pub fn parse_input(input: &str) -> Vec<String> {
-    input_sep_from_string::<String>(input, "\n\n")
+/// The calories of the items carried by each elf.
+///
+/// Every line must be a number or blank, a malformed calorie line is reported instead of
+/// being skipped.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u32>>> {
+    let lines = input_from_string_strict::<Line>(input)?;
+    Ok(lines
+        .split(|line| line.0.is_none())
+        .filter(|group| !group.is_empty())
+        .map(|group| group.iter().filter_map(|line| line.0).collect())
+        .collect())
 }
 
-fn group_sums(input: &[String]) -> Vec<u32> {
-    input
-        .iter()
-        .map(|l| l.lines().filter_map(|s| s.parse::<u32>().ok()).sum())
-        .collect()
+fn group_sums(input: &[Vec<u32>]) -> Vec<u32> {
+    input.iter().map(|group| group.iter().sum()).collect()
 }
 
-fn get_groups() -> Vec<u32> {
-    let data = fs::read_to_string("assets/day01.txt").expect("Could not load file");
-    let input = parse_input(&data);
-    group_sums(&input)
+fn get_groups(input: &str) -> Result<Vec<u32>> {
+    Ok(group_sums(&parse_input(input)?))
 }
 
-pub fn process_a(input: &[u32]) -> u32 {
-    *input.iter().max().unwrap()
+pub fn process_a(input: &[u32]) -> Result<u32> {
+    input
+        .iter()
+        .max()
+        .copied()
+        .ok_or_else(|| AocError::invalid_state("There are no elves"))
 }
 
 pub fn process_b(input: &[u32]) -> u32 {
-    let mut cloned_input = input.iter().copied().collect::<Vec<u32>>();
+    let mut cloned_input = input.to_vec();
     cloned_input.sort();
     cloned_input.into_iter().rev().take(3).sum()
 }
@@ -42,7 +61,7 @@ pub fn process_b(input: &[u32]) -> u32 {
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "1000
+    const RAW_INPUT: &str = "1000
 2000
 3000
 
@@ -59,17 +78,27 @@ mod test {
 
     #[test]
     fn test_day01_parta_sample() {
-        let input = parse_input(RAW_INPUT);
+        let input = parse_input(RAW_INPUT).unwrap();
         let groups = group_sums(&input);
-        let res = process_a(&groups);
+        let res = process_a(&groups).unwrap();
         assert_eq!(res, 24000);
     }
 
     #[test]
     fn test_day02_partb_sample() {
-        let input = parse_input(RAW_INPUT);
+        let input = parse_input(RAW_INPUT).unwrap();
         let groups = group_sums(&input);
         let res = process_b(&groups);
         assert_eq!(res, 45000);
     }
+
+    #[test]
+    fn test_day01_bad_calories() {
+        let input = RAW_INPUT.replace("5000", "5OOO");
+        let error = parse_input(&input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse errors on 1 line(s):\n  line 7: invalid digit found in string: `5OOO`"
+        );
+    }
 }
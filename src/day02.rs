@@ -1,35 +1,31 @@
-use std::{cmp::Ordering, fs};
-
-pub fn day02a() -> String {
-    let data = fs::read_to_string("assets/day02.txt").expect("Could not load file");
-    let battles = parse_input(&data);
-    process_input(&battles).to_string()
-}
+use crate::{
+    error::{parse_lines, Result},
+    solution,
+};
+use log::trace;
+use std::{cmp::Ordering, str::FromStr};
+
+solution!(Day02a, 2, A, parse_input, |battles| Ok(process_input(
+    battles
+)));
+solution!(Day02b, 2, B, parse_input_2, |battles| Ok(process_input_2(
+    battles
+)));
 
-pub fn parse_input(input: &str) -> Vec<Battle> {
-    input.lines().map(String::from).map(Battle::from).collect()
+pub fn parse_input(input: &str) -> Result<Vec<Battle>> {
+    parse_lines(input, Battle::from_str)
 }
 
 pub fn process_input(battles: &[Battle]) -> u32 {
     battles.iter().map(|battle| battle.score()).sum()
 }
 
-pub fn day02b() -> String {
-    let data = fs::read_to_string("assets/day02.txt").expect("Could not load file");
-    let battles = parse_input_2(&data);
-    process_input_2(&battles).to_string()
-}
-
-pub fn parse_input_2(input: &str) -> Vec<BattleTwo> {
-    input
-        .lines()
-        .map(String::from)
-        .map(BattleTwo::from)
-        .collect()
+pub fn parse_input_2(input: &str) -> Result<Vec<BattleTwo>> {
+    parse_lines(input, BattleTwo::from_str)
 }
 
 pub fn process_input_2(battles: &[BattleTwo]) -> u32 {
-    dbg!(&battles);
+    trace!("{battles:?}");
     battles
         .iter()
         .map(|battle| battle.our_play() as u32 + battle.outcome as u32)
@@ -43,16 +39,18 @@ pub enum Shape {
     Scissors = 3,
 }
 
-impl From<char> for Shape {
-    fn from(item: char) -> Self {
+impl TryFrom<char> for Shape {
+    type Error = String;
+
+    fn try_from(item: char) -> Result<Self, Self::Error> {
         match item {
-            'A' => Shape::Rock,
-            'B' => Shape::Paper,
-            'C' => Shape::Scissors,
-            'X' => Shape::Rock,
-            'Y' => Shape::Paper,
-            'Z' => Shape::Scissors,
-            _ => panic!("Cannot parse shape '{}'.", item),
+            'A' => Ok(Shape::Rock),
+            'B' => Ok(Shape::Paper),
+            'C' => Ok(Shape::Scissors),
+            'X' => Ok(Shape::Rock),
+            'Y' => Ok(Shape::Paper),
+            'Z' => Ok(Shape::Scissors),
+            _ => Err(format!("Cannot parse shape '{}'", item)),
         }
     }
 }
@@ -109,62 +107,72 @@ pub enum Outcome {
     Win = 6,
 }
 
-impl From<char> for Outcome {
-    fn from(item: char) -> Self {
+impl TryFrom<char> for Outcome {
+    type Error = String;
+
+    fn try_from(item: char) -> Result<Self, Self::Error> {
         match item {
-            'X' => Outcome::Loss,
-            'Y' => Outcome::Draw,
-            'Z' => Outcome::Win,
-            _ => panic!("Cannot parse shape '{}'.", item),
+            'X' => Ok(Outcome::Loss),
+            'Y' => Ok(Outcome::Draw),
+            'Z' => Ok(Outcome::Win),
+            _ => Err(format!("Cannot parse outcome '{}'", item)),
         }
     }
 }
 
-#[derive(Debug)]
+/// Split an entry like `A Y` into its two letters.
+fn split_entry(item: &str) -> Result<(char, char), String> {
+    match item.chars().collect::<Vec<_>>()[..] {
+        [first, ' ', second] => Ok((first, second)),
+        _ => Err(String::from("Invalid entry")),
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Battle {
     theirs: Shape,
     ours: Shape,
 }
 
-impl From<String> for Battle {
-    fn from(item: String) -> Self {
-        if item.len() != 3 {
-            panic!("Invalid entry: {}.", item)
-        }
-        let mut chars = item.chars();
-        let theirs = Shape::from(chars.next().unwrap());
-        let ours = Shape::from(chars.nth(1).unwrap());
-        Self { theirs, ours }
+impl FromStr for Battle {
+    type Err = String;
+
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
+        let (theirs, ours) = split_entry(item)?;
+        Ok(Self {
+            theirs: Shape::try_from(theirs)?,
+            ours: Shape::try_from(ours)?,
+        })
     }
 }
 
 impl Battle {
     pub fn score(&self) -> u32 {
         if self.theirs > self.ours {
-            return Outcome::Loss as u32 + self.ours as u32;
+            Outcome::Loss as u32 + self.ours as u32
         } else if self.theirs < self.ours {
-            return Outcome::Win as u32 + self.ours as u32;
+            Outcome::Win as u32 + self.ours as u32
         } else {
             Outcome::Draw as u32 + self.ours as u32
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BattleTwo {
     theirs: Shape,
     outcome: Outcome,
 }
 
-impl From<String> for BattleTwo {
-    fn from(item: String) -> Self {
-        if item.len() != 3 {
-            panic!("Invalid entry: {}.", item)
-        }
-        let mut chars = item.chars();
-        let theirs = Shape::from(chars.next().unwrap());
-        let outcome = Outcome::from(chars.nth(1).unwrap());
-        Self { theirs, outcome }
+impl FromStr for BattleTwo {
+    type Err = String;
+
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
+        let (theirs, outcome) = split_entry(item)?;
+        Ok(Self {
+            theirs: Shape::try_from(theirs)?,
+            outcome: Outcome::try_from(outcome)?,
+        })
     }
 }
 
@@ -190,20 +198,30 @@ impl BattleTwo {
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "A Y
+    const RAW_INPUT: &str = "A Y
 B X
 C Z";
 
     #[test]
     fn test_day02_parta_sample() {
-        let input = parse_input(RAW_INPUT);
+        let input = parse_input(RAW_INPUT).unwrap();
         let res = process_input(&input);
         assert_eq!(res, 15)
     }
 
+    #[test]
+    fn test_day02_invalid_entry() {
+        let error = parse_input("A Y\nB W\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error on line 2: Cannot parse shape 'W': `B W`"
+        );
+        assert!(parse_input_2("A  Y").is_err());
+    }
+
     #[test]
     fn test_day02_partb_sample() {
-        let input = parse_input_2(RAW_INPUT);
+        let input = parse_input_2(RAW_INPUT).unwrap();
         let res = process_input_2(&input);
         assert_eq!(res, 12)
     }
@@ -1,50 +1,55 @@
-use std::{collections::HashSet, fs};
+use crate::{
+    error::{parse_lines, AocError, Result},
+    solution,
+};
+use std::collections::HashSet;
 
-pub fn day03a() -> String {
-    let data = fs::read_to_string("assets/day03.txt").expect("Could not load file");
-    let rucksacks = parse_input(&data);
-    let priorities = process_input_a(&rucksacks);
-    priorities.to_string()
-}
-
-pub fn day03b() -> String {
-    let data = fs::read_to_string("assets/day03.txt").expect("Could not load file");
-    let rucksacks = parse_input(&data);
-    let priorities = process_input_b(&rucksacks, 3);
-    priorities.to_string()
-}
+solution!(Day03a, 3, A, parse_input, |rucksacks| process_input_a(
+    rucksacks
+));
+solution!(Day03b, 3, B, parse_input, |rucksacks| process_input_b(
+    rucksacks, 3
+));
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Rucksack {
     left: Vec<char>,
     right: Vec<char>,
     whole: String,
 }
 
-impl From<&str> for Rucksack {
-    fn from(item: &str) -> Self {
-        if item.len() % 2 != 0 {
-            panic!("Invalid length for item {item}: {}", item.len());
+impl TryFrom<&str> for Rucksack {
+    type Error = String;
+
+    fn try_from(item: &str) -> Result<Self, Self::Error> {
+        if !item.len().is_multiple_of(2) {
+            return Err(format!("Odd number of items {}", item.len()));
+        }
+        if let Some(c) = item.chars().find(|c| !c.is_ascii_alphabetic()) {
+            return Err(format!("Invalid item: {c}"));
         }
         let mid = item.len() / 2;
         let (left, right) = item.split_at(mid);
-        Rucksack {
+        Ok(Rucksack {
             left: left.chars().collect(),
             right: right.chars().collect(),
             whole: String::from(item),
-        }
+        })
     }
 }
 
 impl Rucksack {
-    pub fn common_item(&self) -> char {
-        let left: HashSet<char> = self.left.iter().map(|&c| c).collect();
-        let right: HashSet<char> = self.right.iter().map(|&c| c).collect();
-        let intersection: Vec<char> = left.intersection(&right).map(|&c| c).collect();
-        if intersection.len() != 1 {
-            panic!("Too many common items in the rucksack: {:?}", intersection)
+    pub fn common_item(&self) -> Result<char> {
+        let left: HashSet<char> = self.left.iter().copied().collect();
+        let right: HashSet<char> = self.right.iter().copied().collect();
+        let intersection: Vec<char> = left.intersection(&right).copied().collect();
+        match intersection[..] {
+            [item] => Ok(item),
+            _ => Err(AocError::invalid_state(format!(
+                "Expected one common item in the rucksack {}, found {:?}",
+                self.whole, intersection
+            ))),
         }
-        *intersection.first().unwrap()
     }
 
     pub fn to_hashset(&self) -> HashSet<char> {
@@ -55,35 +60,36 @@ impl Rucksack {
 // To help prioritize item rearrangement, every item type can be converted to a priority:
 //     Lowercase item types a through z have priorities 1 through 26.
 //     Uppercase item types A through Z have priorities 27 through 52.
-pub fn priority(item: char) -> u32 {
+pub fn priority(item: char) -> Result<u32> {
     // [src/main.rs:2] 'a' as u8 = 97
     // [src/main.rs:3] 'z' as u8 = 122
     // [src/main.rs:4] 'A' as u8 = 65
     // [src/main.rs:5] 'Z' as u8 = 90
     let value = item as u32;
     if (97..=122).contains(&value) {
-        return value - 96;
+        return Ok(value - 96);
     }
     if (65..=90).contains(&value) {
-        return value - 38;
+        return Ok(value - 38);
     }
 
-    panic!("Invalid item: {item}/{value}")
+    Err(AocError::invalid_state(format!(
+        "Invalid item: {item}/{value}"
+    )))
 }
 
-pub fn parse_input(input: &str) -> Vec<Rucksack> {
-    input.lines().map(Rucksack::from).collect()
+pub fn parse_input(input: &str) -> Result<Vec<Rucksack>> {
+    parse_lines(input, Rucksack::try_from)
 }
 
-pub fn process_input_a(rucksacks: &[Rucksack]) -> u32 {
+pub fn process_input_a(rucksacks: &[Rucksack]) -> Result<u32> {
     rucksacks
         .iter()
-        .map(|r| r.common_item())
-        .map(|i| priority(i))
+        .map(|r| r.common_item().and_then(priority))
         .sum()
 }
 
-pub fn process_input_b(rucksacks: &[Rucksack], size: usize) -> u32 {
+pub fn process_input_b(rucksacks: &[Rucksack], size: usize) -> Result<u32> {
     let mut badge_sum = 0;
 
     // Split the rucksacks into groups.
@@ -92,7 +98,10 @@ pub fn process_input_b(rucksacks: &[Rucksack], size: usize) -> u32 {
         let mut iter = group.iter();
 
         // Check the first rucksack.
-        let mut group_badge = HashSet::from(iter.next().unwrap().to_hashset());
+        let mut group_badge = match iter.next() {
+            Some(first) => first.to_hashset(),
+            None => continue,
+        };
 
         // Look for the common items with the other ricksacks of the group.
         for next in iter {
@@ -102,22 +111,26 @@ pub fn process_input_b(rucksacks: &[Rucksack], size: usize) -> u32 {
         }
 
         // Ensure there is a single badge at the end of the process.
-        if group_badge.len() != 1 {
-            panic!("Too many badges in the group: {:?}", group_badge)
+        let group_badge_vec = group_badge.iter().copied().collect::<Vec<char>>();
+        match group_badge_vec[..] {
+            // Add its priority value to the total.
+            [badge] => badge_sum += priority(badge)?,
+            _ => {
+                return Err(AocError::invalid_state(format!(
+                    "Expected one badge in the group, found {:?}",
+                    group_badge_vec
+                )))
+            }
         }
-
-        // Add its priority value to the total.
-        let group_badge_vec = group_badge.iter().map(|&c| c).collect::<Vec<char>>();
-        badge_sum += priority(*group_badge_vec.first().unwrap());
     }
-    badge_sum
+    Ok(badge_sum)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "vJrwpWtwJgWrhcsFMMfFFhFp
+    const RAW_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
@@ -127,15 +140,32 @@ CrZsJsPPZsGzwwsLwLmpwMDw
 
     #[test]
     fn test_day03a_sample() {
-        let rucksacks = parse_input(RAW_INPUT);
-        let priorities = process_input_a(&rucksacks);
+        let rucksacks = parse_input(RAW_INPUT).unwrap();
+        let priorities = process_input_a(&rucksacks).unwrap();
         assert_eq!(priorities, 157)
     }
 
     #[test]
     fn test_day03b_sample() {
-        let rucksacks = parse_input(RAW_INPUT);
-        let priorities = process_input_b(&rucksacks, 3);
+        let rucksacks = parse_input(RAW_INPUT).unwrap();
+        let priorities = process_input_b(&rucksacks, 3).unwrap();
         assert_eq!(priorities, 70)
     }
+
+    #[test]
+    fn test_day03_invalid_rucksack() {
+        let error = parse_input("vJrwpWtwJgWrhcsFMMfFFhFp\nabc\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error on line 2: Odd number of items 3: `abc`"
+        );
+        let error = parse_input("ab1d\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error on line 1: Invalid item: 1: `ab1d`"
+        );
+        let rucksacks = parse_input("abcd\n").unwrap();
+        assert!(process_input_a(&rucksacks).is_err());
+        assert!(priority('1').is_err());
+    }
 }
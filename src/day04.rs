@@ -1,28 +1,24 @@
-use std::{collections::HashSet, fs, str::FromStr};
+use crate::{
+    error::{parse_lines, Result},
+    nomstr, solution,
+};
+use std::{collections::HashSet, str::FromStr};
 
 use nom::{
     character::complete::{char, u32},
     combinator::map,
-    error::Error,
     sequence::separated_pair,
-    Finish, IResult,
+    IResult,
 };
 
-pub fn day04a() -> String {
-    let data = fs::read_to_string("assets/day04.txt").expect("Could not load file");
-    let pairs = parse_input_a(&data);
-    let overlaps: u32 = process_input_a(&pairs);
-    overlaps.to_string()
-}
-
-pub fn day04b() -> String {
-    let data = fs::read_to_string("assets/day04.txt").expect("Could not load file");
-    let pairs = parse_input_a(&data);
-    let partial_overlaps: u32 = process_input_b(&pairs);
-    partial_overlaps.to_string()
-}
+solution!(Day04a, 4, A, parse_input_a, |pairs| Ok(process_input_a(
+    pairs
+)));
+solution!(Day04b, 4, B, parse_input_a, |pairs| Ok(process_input_b(
+    pairs
+)));
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Assignement {
     section: HashSet<u32>,
 }
@@ -50,21 +46,9 @@ impl Assignement {
     }
 }
 
-impl FromStr for Assignement {
-    type Err = Error<String>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match Assignement::parse(s).finish() {
-            Ok((_, item)) => Ok(item),
-            Err(Error { input, code }) => Err(Error {
-                input: input.to_string(),
-                code,
-            }),
-        }
-    }
-}
+nomstr!(Assignement);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pair(Assignement, Assignement);
 
 // impl From<&str> for Pair {
@@ -85,20 +69,6 @@ pub struct Pair(Assignement, Assignement);
 //     }
 // }
 
-impl FromStr for Pair {
-    type Err = Error<String>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match Pair::parse(s).finish() {
-            Ok((_, item)) => Ok(item),
-            Err(Error { input, code }) => Err(Error {
-                input: input.to_string(),
-                code,
-            }),
-        }
-    }
-}
-
 impl Pair {
     pub fn parse(i: &str) -> IResult<&str, Pair> {
         map(
@@ -106,6 +76,7 @@ impl Pair {
             |(left, right)| Pair(left, right),
         )(i)
     }
+
     pub fn overlap(&self) -> bool {
         self.0.section.is_superset(&self.1.section) || self.1.section.is_superset(&self.0.section)
     }
@@ -116,8 +87,10 @@ impl Pair {
     }
 }
 
-pub fn parse_input_a(input: &str) -> Vec<Pair> {
-    input.lines().map(|l| l.parse::<Pair>().unwrap()).collect()
+nomstr!(Pair);
+
+pub fn parse_input_a(input: &str) -> Result<Vec<Pair>> {
+    parse_lines(input, Pair::from_str)
 }
 
 pub fn process_input_a(pairs: &[Pair]) -> u32 {
@@ -135,7 +108,7 @@ pub fn process_input_b(pairs: &[Pair]) -> u32 {
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "2-4,6-8
+    const RAW_INPUT: &str = "2-4,6-8
 2-3,4-5
 5-7,7-9
 2-8,3-7
@@ -145,15 +118,25 @@ mod test {
 
     #[test]
     fn test_day04a_sample() {
-        let pairs = parse_input_a(RAW_INPUT);
+        let pairs = parse_input_a(RAW_INPUT).unwrap();
         let overlaps: u32 = process_input_a(&pairs);
         assert_eq!(overlaps, 2)
     }
 
     #[test]
     fn test_day04b_sample() {
-        let pairs = parse_input_a(RAW_INPUT);
+        let pairs = parse_input_a(RAW_INPUT).unwrap();
         let partial_overlaps = process_input_b(&pairs);
         assert_eq!(partial_overlaps, 4)
     }
+
+    #[test]
+    fn test_day04_trailing_input() {
+        let error = "2-4,6-8x".parse::<Pair>().unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+        assert_eq!(
+            parse_input_a("2-4,6-8\n2-3,4\n").unwrap_err().to_string(),
+            "Syntax error: Char failed at line 2, column 6\n  2-3,4\n       ^"
+        );
+    }
 }
@@ -1,31 +1,33 @@
+use crate::{
+    error::{parse_lines, AocError, Result},
+    solution,
+    visualize::{Frame, NullVisualizer, Visualizer},
+};
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::fs;
+use std::{fmt::Display, str::FromStr};
 
-pub fn day05a() -> String {
-    let data = fs::read_to_string("assets/day05.txt").expect("Could not load file");
-    let (mut stacks, moves) = parse_input_a(&data);
-    process_input_a(&mut stacks, &moves)
-}
-
-pub fn day05b() -> String {
-    let data = fs::read_to_string("assets/day05.txt").expect("Could not load file");
-    let (mut stacks, moves) = parse_input_a(&data);
-    process_input_b(&mut stacks, &moves)
-}
+solution!(Day05a, 5, A, parse_input_a, |(stacks, moves)| {
+    process_input_a(stacks, moves, &mut NullVisualizer)
+});
+solution!(Day05b, 5, B, parse_input_a, |(stacks, moves)| {
+    process_input_b(stacks, moves, &mut NullVisualizer)
+});
 
 #[derive(Debug, Clone, Copy)]
 pub struct Crate(char);
 
-impl From<&str> for Crate {
-    fn from(item: &str) -> Self {
+impl FromStr for Crate {
+    type Err = String;
+
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"\[([[:upper:]])\]\s?").unwrap();
+            static ref RE: Regex = Regex::new(r"^\[([[:upper:]])\]\s?$").unwrap();
         }
-        if let Some(caps) = RE.captures(item) {
-            return Crate(caps.get(1).unwrap().as_str().chars().nth(0).unwrap());
-        }
-        panic!("Cannot parse move: `{item}`.")
+        RE.captures(item)
+            .and_then(|caps| caps[1].chars().next())
+            .map(Crate)
+            .ok_or_else(|| format!("Cannot parse crate: `{item}`"))
     }
 }
 
@@ -35,7 +37,7 @@ impl From<&str> for Crate {
 // #[derive(Debug)]
 // pub struct Supplies(Vec<Stack>);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Move {
     count: u32,
     from: usize,
@@ -43,111 +45,183 @@ pub struct Move {
 }
 
 // move 8 from 7 to 1
-impl From<&str> for Move {
-    fn from(item: &str) -> Self {
+impl FromStr for Move {
+    type Err = String;
+
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
         lazy_static! {
             static ref RE: Regex = Regex::new(
                 r#"(?x)
+        ^
         move
         \s
         (?P<move>\d+)
         \s
         from
         \s
-        (?P<from>\d+)
+        (?P<from>[1-9]\d*)
         \s
         to
         \s
-        (?P<to>\d+)
+        (?P<to>[1-9]\d*)
+        $
         "#,
             )
             .unwrap();
         }
-        if let Some(caps) = RE.captures(item) {
-            let count = caps.name("move").unwrap().as_str().parse::<u32>().unwrap();
-            let from = caps
-                .name("from")
-                .unwrap()
-                .as_str()
-                .parse::<usize>()
-                .unwrap()
-                - 1;
-            let to = caps.name("to").unwrap().as_str().parse::<usize>().unwrap() - 1;
-            return Move { count, from, to };
-        }
-        panic!("Cannot parse move: `{item}`.")
+        let caps = RE
+            .captures(item)
+            .ok_or_else(|| String::from("Cannot parse move"))?;
+        let number = |name: &str| caps[name].parse::<usize>().map_err(|e| e.to_string());
+        Ok(Move {
+            count: caps["move"].parse::<u32>().map_err(|e| e.to_string())?,
+            from: number("from")? - 1,
+            to: number("to")? - 1,
+        })
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.from + 1,
+            self.to + 1
+        )
     }
 }
 
-pub fn parse_input_a(input: &str) -> (Vec<Vec<Crate>>, Vec<Move>) {
-    let split_input: Vec<&str> = input.split("\n\n").collect();
-    let raw_stacks = split_input.first().unwrap();
-    let stacks = parse_stacks(raw_stacks);
-    let raw_moves = split_input.last().unwrap();
-    let moves = parse_moves(raw_moves);
-    (stacks, moves)
+pub fn parse_input_a(input: &str) -> Result<(Vec<Vec<Crate>>, Vec<Move>)> {
+    let (raw_stacks, raw_moves) = input
+        .split_once("\n\n")
+        .ok_or_else(|| AocError::parse(1, "Missing the blank line between stacks and moves"))?;
+    let stacks = parse_stacks(raw_stacks)?;
+    let moves =
+        parse_moves(raw_moves).map_err(|e| e.shift_lines(raw_stacks.lines().count() + 1))?;
+    Ok((stacks, moves))
 }
 
-pub fn process_input_a(stacks: &mut Vec<Vec<Crate>>, moves: &[Move]) -> String {
+pub fn process_input_a(
+    stacks: &mut [Vec<Crate>],
+    moves: &[Move],
+    visualizer: &mut dyn Visualizer,
+) -> Result<String> {
+    visualizer.draw(&|| Frame::new("Start", render_stacks(stacks)));
     for m in moves {
         for _ in 0..m.count {
-            let crate_ = stacks[m.from].pop().unwrap();
-            stacks[m.to].push(crate_);
+            let crate_ = pop_crate(stacks, m.from)?;
+            push_crates(stacks, m.to, &[crate_])?;
         }
+        visualizer.draw(&|| Frame::new(m.to_string(), render_stacks(stacks)));
     }
-    message(&stacks)
+    message(stacks)
 }
 
-pub fn process_input_b(stacks: &mut Vec<Vec<Crate>>, moves: &[Move]) -> String {
+pub fn process_input_b(
+    stacks: &mut [Vec<Crate>],
+    moves: &[Move],
+    visualizer: &mut dyn Visualizer,
+) -> Result<String> {
+    visualizer.draw(&|| Frame::new("Start", render_stacks(stacks)));
     for m in moves {
         let mut chunks: Vec<Crate> = Vec::new();
         for _ in 0..m.count {
-            let crate_ = stacks[m.from].pop().unwrap();
+            let crate_ = pop_crate(stacks, m.from)?;
             chunks.insert(0, crate_);
         }
-        stacks[m.to].extend_from_slice(&chunks);
+        push_crates(stacks, m.to, &chunks)?;
+        visualizer.draw(&|| Frame::new(m.to_string(), render_stacks(stacks)));
     }
-    message(&stacks)
+    message(stacks)
 }
 
-pub fn parse_stacks(input: &str) -> Vec<Vec<Crate>> {
-    let raw_stacks = input
-        .lines()
+/// Draw the stacks like the puzzle input does, with their labels underneath.
+pub fn render_stacks(stacks: &[Vec<Crate>]) -> String {
+    let height = stacks.iter().map(|s| s.len()).max().unwrap_or_default();
+    let mut rows = (0..height)
         .rev()
-        .skip(1)
-        .map(String::from)
-        .collect::<Vec<String>>();
-    let stack_count = raw_stacks.first().unwrap().split_whitespace().count();
+        .map(|level| {
+            let crates = stacks
+                .iter()
+                .map(|stack| {
+                    stack
+                        .get(level)
+                        .map_or(String::from("   "), |c| format!("[{}]", c.0))
+                })
+                .collect::<Vec<_>>();
+            crates.join(" ").trim_end().to_string()
+        })
+        .collect::<Vec<_>>();
+    let labels = (1..=stacks.len())
+        .map(|n| format!(" {n} "))
+        .collect::<Vec<_>>();
+    rows.push(labels.join(" ").trim_end().to_string());
+    rows.join("\n") + "\n"
+}
+
+fn pop_crate(stacks: &mut [Vec<Crate>], from: usize) -> Result<Crate> {
+    stacks
+        .get_mut(from)
+        .and_then(|stack| stack.pop())
+        .ok_or_else(|| AocError::invalid_state(format!("No crate to move from stack {}", from + 1)))
+}
+
+fn push_crates(stacks: &mut [Vec<Crate>], to: usize, crates: &[Crate]) -> Result<()> {
+    stacks
+        .get_mut(to)
+        .ok_or_else(|| AocError::invalid_state(format!("There is no stack {}", to + 1)))?
+        .extend_from_slice(crates);
+    Ok(())
+}
+
+pub fn parse_stacks(input: &str) -> Result<Vec<Vec<Crate>>> {
+    let raw_stacks = input.lines().collect::<Vec<&str>>();
+    let (labels, raw_stacks) = raw_stacks
+        .split_last()
+        .ok_or_else(|| AocError::parse(1, "Missing the stacks"))?;
+    let stack_count = labels.split_whitespace().count();
     let mut stacks: Vec<Vec<Crate>> = vec![vec![]; stack_count];
-    for stack_line in raw_stacks {
+    for (n, stack_line) in raw_stacks.iter().enumerate().rev() {
         let line = stack_line.chars().collect::<Vec<char>>();
         for (i, crate_) in line.chunks(4).enumerate() {
             let c: String = crate_.iter().collect();
             if !c.trim().is_empty() {
-                stacks[i].push(Crate::from(c.as_str()));
+                let crate_ = c.parse::<Crate>().map_err(|e| AocError::parse(n + 1, e))?;
+                stacks
+                    .get_mut(i)
+                    .ok_or_else(|| AocError::parse(n + 1, format!("There is no stack {}", i + 1)))?
+                    .push(crate_);
             }
         }
     }
-    stacks
+    Ok(stacks)
 }
 
-pub fn parse_moves(input: &str) -> Vec<Move> {
-    input.lines().map(Move::from).collect::<Vec<Move>>()
+pub fn parse_moves(input: &str) -> Result<Vec<Move>> {
+    parse_lines(input, Move::from_str)
 }
 
-pub fn message(stacks: &Vec<Vec<Crate>>) -> String {
-    let mut message = String::new();
-    for stack in stacks {
-        message.push(stack.last().unwrap().0);
-    }
-    message
+pub fn message(stacks: &[Vec<Crate>]) -> Result<String> {
+    stacks
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| {
+            stack
+                .last()
+                .map(|c| c.0)
+                .ok_or_else(|| AocError::invalid_state(format!("Stack {} is empty", i + 1)))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::visualize::Recorder;
 
-    const RAW_INPUT: &'static str = "    [D]
+    const RAW_INPUT: &str = "    [D]
 [N] [C]
 [Z] [M] [P]
  1   2   3
@@ -160,15 +234,56 @@ move 1 from 1 to 2
 
     #[test]
     fn test_day05a_sample() {
-        let (mut stacks, moves) = parse_input_a(RAW_INPUT);
-        let message = process_input_a(&mut stacks, &moves);
+        let (mut stacks, moves) = parse_input_a(RAW_INPUT).unwrap();
+        let message = process_input_a(&mut stacks, &moves, &mut NullVisualizer).unwrap();
         assert_eq!(message, String::from("CMZ"));
     }
 
     #[test]
     fn test_day05b_sample() {
-        let (mut stacks, moves) = parse_input_a(RAW_INPUT);
-        let message = process_input_b(&mut stacks, &moves);
+        let (mut stacks, moves) = parse_input_a(RAW_INPUT).unwrap();
+        let message = process_input_b(&mut stacks, &moves, &mut NullVisualizer).unwrap();
         assert_eq!(message, String::from("MCD"));
     }
+
+    #[test]
+    fn test_day05_frames() {
+        let (mut stacks, moves) = parse_input_a(RAW_INPUT).unwrap();
+        let mut recorder = Recorder::default();
+        process_input_a(&mut stacks, &moves, &mut recorder).unwrap();
+        assert_eq!(recorder.frames.len(), 5);
+        assert_eq!(
+            recorder.frames[0].picture,
+            RAW_INPUT.split("\n\n").next().unwrap().to_string() + "\n"
+        );
+        assert_eq!(
+            recorder.frames[1],
+            Frame::new(
+                "move 1 from 2 to 1",
+                "[D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+"
+            )
+        );
+    }
+
+    #[test]
+    fn test_day05_errors() {
+        let input = RAW_INPUT.replace("move 3 from 1", "move 3 frm 1");
+        assert_eq!(
+            parse_input_a(&input).unwrap_err().to_string(),
+            "Parse error on line 7: Cannot parse move: `move 3 frm 1 to 3`"
+        );
+
+        let input = RAW_INPUT.replace("move 3 from 1", "move 4 from 1");
+        let (mut stacks, moves) = parse_input_a(&input).unwrap();
+        assert_eq!(
+            process_input_a(&mut stacks, &moves, &mut NullVisualizer)
+                .unwrap_err()
+                .to_string(),
+            "Invalid puzzle state: No crate to move from stack 1"
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use camino::Utf8PathBuf;
 use indexmap::IndexMap;
+use log::debug;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while1},
@@ -7,20 +8,36 @@ use nom::{
     sequence::{preceded, separated_pair},
     Finish, IResult,
 };
-use std::{cell::RefCell, fs, rc::Rc};
+use serde::Deserialize;
+use std::{cell::RefCell, rc::Rc};
 
-pub fn day07a() -> String {
-    let data = fs::read_to_string("assets/day07.txt").expect("Could not load file");
-    let root = parse_input_a(&data);
-    let sum = process_input_a(root);
-    sum.to_string()
+use crate::{
+    error::{parse_lines, AocError, Result},
+    solution,
+};
+
+solution!(Day07a, 7, A, parse_input_a, |root| Ok(process_input_a(
+    root
+)));
+solution!(Day07b, 7, B, params, parse_input_a, |root, params| {
+    process_input_b(root, &params.day07)
+});
+
+/// The sizes of the disk of the device and of the free space the update needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Disk {
+    pub total: u64,
+    pub needed: u64,
 }
 
-pub fn day07b() -> String {
-    let data = fs::read_to_string("assets/day07.txt").expect("Could not load file");
-    let root = parse_input_a(&data);
-    let sum = process_input_b(root);
-    sum.to_string()
+impl Default for Disk {
+    fn default() -> Self {
+        Disk {
+            total: 70_000_000,
+            needed: 30_000_000,
+        }
+    }
 }
 
 pub struct Filesystem {}
@@ -142,15 +159,15 @@ fn all_dirs(n: NodeHandle) -> Box<dyn Iterator<Item = NodeHandle>> {
     )
 }
 
-pub fn parse_input_a(input: &str) -> Rc<RefCell<Node>> {
-    let lines = input
-        .lines()
-        .map(|l| all_consuming(parse_line)(l).finish().unwrap().1);
+pub fn parse_input_a(input: &str) -> Result<Rc<RefCell<Node>>> {
+    let lines = parse_lines(input, |l| {
+        all_consuming(parse_line)(l).finish().map(|(_, line)| line)
+    })?;
 
     let root = Rc::new(RefCell::new(Node::default()));
     let mut node = root.clone();
 
-    for line in lines {
+    for (n, line) in lines.into_iter().enumerate() {
         match line {
             Line::Command(cmd) => match cmd {
                 Command::Ls => {
@@ -161,7 +178,9 @@ pub fn parse_input_a(input: &str) -> Rc<RefCell<Node>> {
                         // ignore, we're already there
                     }
                     ".." => {
-                        let parent = node.borrow().parent.clone().unwrap();
+                        let parent = node.borrow().parent.clone().ok_or_else(|| {
+                            AocError::parse(n + 1, "Cannot move out of the root directory")
+                        })?;
                         node = parent;
                     }
                     _ => {
@@ -184,38 +203,40 @@ pub fn parse_input_a(input: &str) -> Rc<RefCell<Node>> {
         }
     }
 
-    root
+    Ok(root)
 }
-pub fn process_input_a(root: Rc<RefCell<Node>>) -> u64 {
-    let sum = all_dirs(root)
+pub fn process_input_a(root: &Rc<RefCell<Node>>) -> u64 {
+    all_dirs(root.clone())
         .map(|d| d.borrow().total_size())
         .filter(|&s| s <= 100_000)
-        .sum::<u64>();
-    sum
+        .sum::<u64>()
 }
 
-pub fn process_input_b(root: Rc<RefCell<Node>>) -> u64 {
-    let total_space = 70000000_u64;
+pub fn process_input_b(root: &Rc<RefCell<Node>>, disk: &Disk) -> Result<u64> {
+    let total_space = disk.total;
     let used_space = root.borrow().total_size();
-    let free_space = total_space.checked_sub(dbg!(used_space)).unwrap();
-    let needed_free_space = 30000000_u64;
-    let minimum_space_to_free = needed_free_space.checked_sub(free_space).unwrap();
-
-    let removed_dir_size = all_dirs(root)
+    debug!("Used space: {used_space}");
+    let free_space = total_space
+        .checked_sub(used_space)
+        .ok_or_else(|| AocError::invalid_state("The disk is too small for the filesystem"))?;
+    let needed_free_space = disk.needed;
+    let minimum_space_to_free = needed_free_space
+        .checked_sub(free_space)
+        .ok_or_else(|| AocError::invalid_state("There is already enough free space"))?;
+
+    let removed_dir_size = all_dirs(root.clone())
         .map(|d| d.borrow().total_size())
         .filter(|&s| s >= minimum_space_to_free)
-        .inspect(|s| {
-            dbg!(s);
-        })
+        .inspect(|s| debug!("Candidate directory: {s}"))
         .min();
-    removed_dir_size.unwrap()
+    removed_dir_size.ok_or_else(|| AocError::invalid_state("No directory is big enough"))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "$ cd /
+    const RAW_INPUT: &str = "$ cd /
 $ ls
 dir a
 14848514 b.txt
@@ -242,15 +263,15 @@ $ ls
 
     #[test]
     fn test_day07a_sample() {
-        let root = parse_input_a(RAW_INPUT);
-        let sum = process_input_a(root);
+        let root = parse_input_a(RAW_INPUT).unwrap();
+        let sum = process_input_a(&root);
         assert_eq!(sum, 95437)
     }
 
     #[test]
     fn test_day07b_sample() {
-        let root = parse_input_a(RAW_INPUT);
-        let sum = process_input_b(root);
+        let root = parse_input_a(RAW_INPUT).unwrap();
+        let sum = process_input_b(&root, &Disk::default()).unwrap();
         assert_eq!(sum, 24933642)
     }
 }
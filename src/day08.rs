@@ -1,155 +1,89 @@
-use std::fs;
+use log::trace;
 
-pub fn day08a() -> String {
-    let data = fs::read_to_string("assets/day08.txt").expect("Could not load file");
-    let grid = parse_input_a(&data);
-    let visible = process_input_a(grid);
-    visible.to_string()
-}
-
-pub fn day08b() -> String {
-    let data = fs::read_to_string("assets/day08.txt").expect("Could not load file");
-    let grid = parse_input_a(&data);
-    let highest_score = process_input_b(grid);
-    highest_score.to_string()
-}
+use crate::{error::Result, geom::Point2, grid::Grid, solution};
 
-pub fn parse_input_a(input: &str) -> Vec<Vec<u8>> {
-    let grid: Vec<Vec<u8>> = input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).unwrap() as u8)
-                .collect::<Vec<u8>>()
-        })
-        .collect();
+solution!(Day08a, 8, A, parse_input_a, |grid| Ok(process_input_a(
     grid
+)));
+solution!(Day08b, 8, B, parse_input_a, |grid| Ok(process_input_b(
+    grid
+)));
+
+pub fn parse_input_a(input: &str) -> Result<Grid<u8>> {
+    Grid::parse_chars(input, |c| {
+        c.to_digit(10).map(|d| d as u8).ok_or("Invalid tree height")
+    })
 }
 
-pub fn process_input_a(grid: Vec<Vec<u8>>) -> u32 {
-    let row_count = grid.len();
-    let col_count = grid[0].len();
+pub fn process_input_a(grid: &Grid<u8>) -> u32 {
     let mut visible = 0;
-    for row in 0..row_count {
-        for col in 0..col_count {
-            if is_visible(&grid, row, col) {
-                println!("[{row}, {col}]");
-                visible += 1;
-            }
+    for Point2 { x, y } in grid.positions() {
+        if is_visible(grid, y, x) {
+            trace!("Visible tree at [{x},{y}]");
+            visible += 1;
         }
     }
 
     visible
 }
 
-pub fn process_input_b(grid: Vec<Vec<u8>>) -> u32 {
-    let row_count = grid.len();
-    let col_count = grid[0].len();
-    let mut scores: Vec<u32> = Vec::new();
-    for row in 0..row_count {
-        for col in 0..col_count {
-            scores.push(scenic_score(&grid, row, col));
-        }
-    }
-
-    *scores.iter().max().unwrap()
+pub fn process_input_b(grid: &Grid<u8>) -> u32 {
+    grid.positions()
+        .map(|Point2 { x, y }| scenic_score(grid, y, x))
+        .max()
+        .unwrap_or_default()
 }
 
-pub fn is_visible(grid: &Vec<Vec<u8>>, row: usize, col: usize) -> bool {
-    let row_count = grid.len();
-    let col_count = grid[0].len();
-
+pub fn is_visible(grid: &Grid<u8>, row: usize, col: usize) -> bool {
     // Current tree size.
-    let current_tree = grid[row][col];
+    let current_tree = grid[Point2::new(col, row)];
 
     // Is it on the edge?
-    if row == 0 || row == row_count - 1 || col == 0 || col == col_count - 1 {
+    if row == 0 || row == grid.height() - 1 || col == 0 || col == grid.width() - 1 {
         return true;
     }
 
-    let left: Vec<u8> = grid[row][0..col].iter().map(|&t| t).collect();
-    let right: Vec<u8> = grid[row][col + 1..col_count].iter().map(|&t| t).collect();
-    let mut top: Vec<u8> = Vec::new();
-    for i in 0..row {
-        top.push(grid[i][col]);
-    }
-    // let top: Vec<u8> = (0..row).map(|x| grid[row][col]).collect();
-    let mut bottom: Vec<u8> = Vec::new();
-    for i in row + 1..row_count {
-        bottom.push(grid[i][col]);
-    }
-    // let bottom: Vec<u8> = grid[row + 1..row_count][col].iter().map(|&t| t).collect();
-    if left.iter().all(|&t| t < current_tree)
+    let left = &grid.row(row)[0..col];
+    let right = &grid.row(row)[col + 1..];
+    let top = grid.column(col).take(row).collect::<Vec<_>>();
+    let bottom = grid.column(col).skip(row + 1).collect::<Vec<_>>();
+    left.iter().all(|&t| t < current_tree)
         || right.iter().all(|&t| t < current_tree)
-        || top.iter().all(|&t| t < current_tree)
-        || bottom.iter().all(|&t| t < current_tree)
-    {
-        return true;
-    }
-
-    false
+        || top.iter().all(|&&t| t < current_tree)
+        || bottom.iter().all(|&&t| t < current_tree)
 }
 
-pub fn scenic_score(grid: &Vec<Vec<u8>>, row: usize, col: usize) -> u32 {
-    let row_count = grid.len();
-    let col_count = grid[0].len();
-
+pub fn scenic_score(grid: &Grid<u8>, row: usize, col: usize) -> u32 {
     // Current tree size.
-    let current_tree = grid[row][col];
-
-    // Prepare the tree list for all directions.
-    let left: Vec<u8> = grid[row][0..col].iter().map(|&t| t).collect();
-    let right: Vec<u8> = grid[row][col + 1..col_count].iter().map(|&t| t).collect();
-    let mut top: Vec<u8> = Vec::new();
-    for i in 0..row {
-        top.push(grid[i][col]);
-    }
-    let mut bottom: Vec<u8> = Vec::new();
-    for i in row + 1..row_count {
-        bottom.push(grid[i][col]);
-    }
+    let current_tree = grid[Point2::new(col, row)];
+
+    // Prepare the tree list for all directions, starting from the current tree.
+    let left = grid.row(row)[0..col].iter().rev().collect::<Vec<_>>();
+    let right = grid.row(row)[col + 1..].iter().collect::<Vec<_>>();
+    let top = grid.column(col).take(row).rev().collect::<Vec<_>>();
+    let bottom = grid.column(col).skip(row + 1).collect::<Vec<_>>();
 
     // Compute the scenic score.
-    let mut left_score = 0;
-    for item in left.iter().rev() {
-        left_score += 1;
-        if item >= &current_tree {
-            break;
-        }
-    }
-    let mut right_score = 0;
-    for item in right {
-        right_score += 1;
-        if item >= current_tree {
-            break;
-        }
-    }
-    let mut top_score = 0;
-    for item in top.iter().rev() {
-        top_score += 1;
-        if item >= &current_tree {
-            break;
-        }
-    }
-    let mut bottom_score = 0;
-    for item in bottom {
-        bottom_score += 1;
-        if item >= current_tree {
-            break;
-        }
-    }
-    let score = left_score * right_score * top_score * bottom_score;
-    // println!(
-    //     "[{row}, {col}]: {current_tree} - {left_score}, {right_score}, {top_score}, {bottom_score} => {score}"
-    // );
-    score
+    [left, right, top, bottom]
+        .iter()
+        .map(|trees| {
+            let mut score = 0;
+            for &&tree in trees {
+                score += 1;
+                if tree >= current_tree {
+                    break;
+                }
+            }
+            score
+        })
+        .product()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "30373
+    const RAW_INPUT: &str = "30373
 25512
 65332
 33549
@@ -158,15 +92,15 @@ mod test {
 
     #[test]
     fn test_day08a_sample() {
-        let grid = parse_input_a(RAW_INPUT);
-        let visible = process_input_a(grid);
+        let grid = parse_input_a(RAW_INPUT).unwrap();
+        let visible = process_input_a(&grid);
         assert_eq!(visible, 21);
     }
 
     #[test]
     fn test_day08b_sample() {
-        let grid = parse_input_a(RAW_INPUT);
-        let highest_score = process_input_b(grid);
+        let grid = parse_input_a(RAW_INPUT).unwrap();
+        let highest_score = process_input_b(&grid);
         assert_eq!(highest_score, 8);
     }
 }
@@ -1,145 +1,112 @@
-use std::collections::HashSet;
-use std::fs;
-
-pub fn day09a() -> String {
-    let data = fs::read_to_string("assets/day09.txt").expect("Could not load file");
-    let motions = parse_input_a(&data);
-    let visited = process_input(&motions);
-    visited.to_string()
-}
-
-pub fn day09b() -> String {
-    let data = fs::read_to_string("assets/day09.txt").expect("Could not load file");
-    data
-}
-
-#[derive(Debug)]
-pub enum Direction {
-    U,
-    D,
-    L,
-    R,
-}
-
-impl From<&str> for Direction {
-    fn from(item: &str) -> Self {
-        match item {
-            "U" => Direction::U,
-            "D" => Direction::D,
-            "L" => Direction::L,
-            "R" => Direction::R,
-            _ => panic!("Invalid direction: {item}"),
-        }
+use crate::{
+    error::{parse_lines, Result},
+    geom::{Direction, Point2},
+    grid::SparseGrid,
+    solution,
+    visualize::{Frame, NullVisualizer, Visualizer},
+};
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+solution!(Day09a, 9, A, parse_input_a, |motions| Ok(process_input(
+    motions,
+    &mut NullVisualizer
+)));
+
+pub fn parse_direction(item: &str) -> Result<Direction, String> {
+    match item {
+        "U" => Ok(Direction::Up),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        "R" => Ok(Direction::Right),
+        _ => Err(format!("Invalid direction: {item}")),
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Motion {
     direction: Direction,
     steps: i32,
 }
 
-impl From<&str> for Motion {
-    fn from(item: &str) -> Self {
-        let split = item.split_whitespace().collect::<Vec<&str>>();
-        let direction = split.first().map(|&d| Direction::from(d)).unwrap();
-        let steps = split.last().unwrap().parse::<i32>().unwrap();
-        Motion { direction, steps }
+impl Display for Motion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let direction = match self.direction {
+            Direction::Up => "U",
+            Direction::Down => "D",
+            Direction::Left => "L",
+            Direction::Right => "R",
+        };
+        write!(f, "{direction} {}", self.steps)
     }
 }
 
-#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
-pub struct Position {
-    x: i32,
-    y: i32,
-}
-
-impl Position {
-    pub fn up(&mut self) {
-        self.y += 1;
-    }
-
-    pub fn down(&mut self) {
-        self.y -= 1;
-    }
-
-    pub fn left(&mut self) {
-        self.x -= 1;
-    }
-
-    pub fn right(&mut self) {
-        self.x += 1;
-    }
-
-    pub fn adjacent(&self, other: &Position) -> bool {
-        ((self.x - other.x).abs() <= 1) && ((self.y - other.y).abs() <= 1)
-    }
-
-    pub fn same_row(&self, other: &Position) -> bool {
-        self.x == other.x
-    }
-
-    pub fn same_column(&self, other: &Position) -> bool {
-        self.y == other.y
-    }
-
-    pub fn step(&mut self, direction: &Direction) {
-        match direction {
-            Direction::U => self.up(),
-            Direction::D => self.down(),
-            Direction::L => self.left(),
-            Direction::R => self.right(),
-        }
+impl FromStr for Motion {
+    type Err = String;
+
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
+        let (direction, steps) = item
+            .split_once(' ')
+            .ok_or_else(|| String::from("Invalid motion"))?;
+        Ok(Motion {
+            direction: parse_direction(direction)?,
+            steps: steps.parse::<i32>().map_err(|e| e.to_string())?,
+        })
     }
 }
 
+pub type Position = Point2<i32>;
+
 // pub struct Rope {
 //     tail: Position,
 //     head: Position,
 //     visited: HashSet<Position>,
 // }
 
-pub fn parse_input_a(input: &str) -> Vec<Motion> {
-    input.lines().map(Motion::from).collect::<Vec<Motion>>()
+pub fn parse_input_a(input: &str) -> Result<Vec<Motion>> {
+    parse_lines(input, Motion::from_str)
 }
 
-pub fn process_input(motions: &[Motion]) -> usize {
+pub fn process_input(motions: &[Motion], visualizer: &mut dyn Visualizer) -> usize {
     // Initialize the motion.
-    let mut tail = Position { x: 0, y: 0 };
-    let mut head = Position { x: 0, y: 0 };
+    let mut tail = Position::default();
+    let mut head = Position::default();
     let mut visited: HashSet<Position> = HashSet::new();
-    visited.insert(tail.clone());
+    visited.insert(tail);
 
     for motion in motions {
         for _ in 0..motion.steps {
-            // dbg!(&motion);
-            head.step(&motion.direction);
-            // dbg!(&head, &tail, &head.adjacent(&tail));
-            if head.adjacent(&tail) {
-                continue;
+            head = head.step(motion.direction);
+            // Follow the head, diagonally when not on the same row or column.
+            if head.chebyshev(&tail) > 1 {
+                tail += (head - tail).signum();
+                visited.insert(tail);
             }
-            if !head.same_row(&tail) || !head.same_column(&tail) {
-                let horizontal_diff = (head.x - tail.x).signum();
-                let vertical_diff = (head.y - tail.y).signum();
-                tail.x += horizontal_diff;
-                tail.y += vertical_diff;
-            } else {
-                tail.step(&motion.direction);
-            }
-
-            visited.insert(tail.clone());
-            // dbg!(&tail);
+            visualizer.draw(&|| Frame::new(motion.to_string(), render(&visited, head, tail)));
         }
     }
 
     visited.len()
 }
 
+/// Draw the rope over the positions the tail visited, like the puzzle does.
+pub fn render(visited: &HashSet<Position>, head: Position, tail: Position) -> String {
+    let mut grid = SparseGrid::new();
+    let point = |p: Position| Point2::new(p.x as i64, p.y as i64);
+    for &p in visited {
+        grid.insert(point(p), '#');
+    }
+    grid.insert(point(Position::default()), 's');
+    grid.insert(point(tail), 'T');
+    grid.insert(point(head), 'H');
+    grid.render('.', |&c| c)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::visualize::Recorder;
 
-    const RAW_INPUT: &'static str = "R 4
+    const RAW_INPUT: &str = "R 4
 U 4
 L 3
 D 1
@@ -151,11 +118,29 @@ R 2
 
     #[test]
     fn test_day09a_sample() {
-        let motions = parse_input_a(RAW_INPUT);
-        let visited = process_input(&motions);
+        let motions = parse_input_a(RAW_INPUT).unwrap();
+        let visited = process_input(&motions, &mut NullVisualizer);
         assert_eq!(visited, 13);
     }
 
+    #[test]
+    fn test_day09_frames() {
+        let motions = parse_input_a(RAW_INPUT).unwrap();
+        let mut recorder = Recorder::default();
+        process_input(&motions, &mut recorder);
+        assert_eq!(recorder.frames.len(), 24);
+        assert_eq!(recorder.frames[1], Frame::new("R 4", "sTH\n"));
+        assert_eq!(
+            recorder.frames[23].picture,
+            "..##.
+...##
+.TH##
+....#
+s###.
+"
+        );
+    }
+
     #[test]
     fn test_day09b_sample() {}
 }
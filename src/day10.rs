@@ -1,18 +1,19 @@
-use std::fs;
+use std::str::FromStr;
 
-pub fn day10a() -> String {
-    let data = fs::read_to_string("assets/day10.txt").expect("Could not load file");
-    let instructions = parse_input_a(&data);
-    let combined_signal_strenghs = process_input_a(&instructions);
-    combined_signal_strenghs.to_string()
-}
+use log::debug;
 
-pub fn day10b() -> String {
-    let data = fs::read_to_string("assets/day10.txt").expect("Could not load file");
-    let instructions = parse_input_a(&data);
-    process_input_b(&instructions);
-    0.to_string()
-}
+use crate::{
+    error::{parse_lines, Result},
+    solution,
+    solution::Answer,
+};
+
+solution!(Day10a, 10, A, parse_input_a, |instructions| {
+    Ok(process_input_a(instructions))
+});
+solution!(Day10b, 10, B, parse_input_a, |instructions| {
+    Ok(Answer::picture(process_input_b(instructions)))
+});
 
 #[derive(Debug, Clone, Copy)]
 pub enum Instruction {
@@ -20,25 +21,27 @@ pub enum Instruction {
     Addx(i32),
 }
 
-impl From<&str> for Instruction {
-    fn from(item: &str) -> Self {
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
         if item == "noop" {
-            return Instruction::Noop;
+            return Ok(Instruction::Noop);
+        }
+        match item.split_once(' ') {
+            Some(("addx", value)) => value
+                .parse::<i32>()
+                .map(Instruction::Addx)
+                .map_err(|e| e.to_string()),
+            _ => Err(String::from("Unknown instruction")),
         }
-        let split = item
-            .split_whitespace()
-            .map(String::from)
-            .collect::<Vec<String>>();
-        let value = split.last().unwrap().parse::<i32>().unwrap();
-        Instruction::Addx(value)
     }
 }
 
-pub fn parse_input_a(input: &str) -> Vec<Instruction> {
+pub fn parse_input_a(input: &str) -> Result<Vec<Instruction>> {
     // input.lines().map(Instruction::from).collect()
     let mut instructions: Vec<Instruction> = Vec::new();
-    for line in input.lines() {
-        let instruction = Instruction::from(line);
+    for instruction in parse_lines(input, Instruction::from_str)? {
         match instruction {
             Instruction::Noop => instructions.push(instruction),
             Instruction::Addx(_) => {
@@ -47,7 +50,7 @@ pub fn parse_input_a(input: &str) -> Vec<Instruction> {
             }
         }
     }
-    instructions
+    Ok(instructions)
 }
 
 pub fn process_input_a(instructions: &[Instruction]) -> i32 {
@@ -61,7 +64,7 @@ pub fn process_input_a(instructions: &[Instruction]) -> i32 {
             let current_signal_strength = cycle1 as i32 * registry;
             signal_strength += current_signal_strength;
             special_cycle += 40;
-            println!("[{cycle1}], {registry}, {current_signal_strength}, {signal_strength}");
+            debug!("[{cycle1}], {registry}, {current_signal_strength}, {signal_strength}");
         }
         if let Instruction::Addx(value) = instruction {
             registry += value;
@@ -70,31 +73,35 @@ pub fn process_input_a(instructions: &[Instruction]) -> i32 {
     signal_strength
 }
 
-pub fn process_input_b(instructions: &[Instruction]) {
+/// Draw the CRT screen, one row of 40 pixels per line.
+pub fn process_input_b(instructions: &[Instruction]) -> String {
     let mut registry: i32 = 1;
+    let mut screen = String::new();
 
     for (cycle, instruction) in instructions.iter().enumerate() {
         let cyclemod40 = cycle % 40;
-        if cyclemod40 == 0 {
-            println!("");
+        if cyclemod40 == 0 && cycle > 0 {
+            screen.push('\n');
         }
         if (cyclemod40 as i32) < (registry - 1) || (cyclemod40 as i32) > (registry + 1) {
-            print!(".");
+            screen.push('.');
         } else {
-            print!("#")
+            screen.push('#');
         }
 
         if let Instruction::Addx(value) = instruction {
             registry += value;
         }
     }
+    screen.push('\n');
+    screen
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "addx 15
+    const RAW_INPUT: &str = "addx 15
 addx -11
 addx 6
 addx -3
@@ -244,14 +251,23 @@ noop
 
     #[test]
     fn test_day10a_sample() {
-        let instructions = parse_input_a(RAW_INPUT);
+        let instructions = parse_input_a(RAW_INPUT).unwrap();
         let combined_signal_strenghs = process_input_a(&instructions);
         assert_eq!(combined_signal_strenghs, 13140);
     }
 
     #[test]
     fn test_day10b_sample() {
-        let instructions = parse_input_a(RAW_INPUT);
-        process_input_b(&instructions);
+        let instructions = parse_input_a(RAW_INPUT).unwrap();
+        assert_eq!(
+            process_input_b(&instructions),
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+        );
     }
 }
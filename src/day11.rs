@@ -7,36 +7,33 @@ use nom::{
     },
     error::{Error, ErrorKind},
     multi::separated_list1,
-    sequence::preceded,
-    Finish, IResult,
+    IResult,
 };
-use std::{fs, str::FromStr};
 
-pub fn day11a() -> String {
-    let data = fs::read_to_string("assets/day11.txt").expect("Could not load file");
-    let monkeys = parse_input_a(&data);
-    let inspected = process_input_a(&monkeys, 3, 20);
-    inspected.to_string()
-}
-
-pub fn day11b() -> String {
-    let data = fs::read_to_string("assets/day11.txt").expect("Could not load file");
-    data
-}
+use crate::{
+    error::{AocError, Result},
+    nomstr, solution,
+};
 
-pub fn parse_input_a(input: &str) -> Vec<Monkey> {
-    let raw_monkeys = input
-        .split("\n\n")
-        .map(String::from)
-        .collect::<Vec<String>>();
-    raw_monkeys
-        .iter()
-        .map(|l| l.trim())
-        .map(|l| l.parse::<Monkey>().unwrap())
-        .collect::<Vec<Monkey>>()
+solution!(Day11a, 11, A, parse_input_a, |monkeys| process_input_a(
+    monkeys, 3, 20
+));
+
+pub fn parse_input_a(input: &str) -> Result<Vec<Monkey>> {
+    // Each monkey is parsed on its own: the operation parser looks ahead for its symbol.
+    let mut offset = 0;
+    let mut monkeys = Vec::new();
+    for raw_monkey in input.split("\n\n") {
+        let monkey = raw_monkey
+            .parse::<Monkey>()
+            .map_err(|e| AocError::from(e).shift_lines(offset))?;
+        monkeys.push(monkey);
+        offset += raw_monkey.lines().count() + 1;
+    }
+    Ok(monkeys)
 }
 
-pub fn process_input_a(monkeys: &[Monkey], factor: u64, rounds: u32) -> u32 {
+pub fn process_input_a(monkeys: &[Monkey], factor: u64, rounds: u32) -> Result<u32> {
     let mut items = vec![vec![]; monkeys.len()];
     let mut inspected = vec![0; monkeys.len()];
 
@@ -54,7 +51,10 @@ pub fn process_input_a(monkeys: &[Monkey], factor: u64, rounds: u32) -> u32 {
             // Throw its items.
             for j in 0..items[i].len() {
                 let (item, to) = monkey.throw_item_to(items[i][j], factor);
-                items[to].push(item);
+                items
+                    .get_mut(to)
+                    .ok_or_else(|| AocError::invalid_state(format!("There is no monkey {to}")))?
+                    .push(item);
                 inspected[i] += 1;
             }
             // Remove the monkey's items.
@@ -64,10 +64,10 @@ pub fn process_input_a(monkeys: &[Monkey], factor: u64, rounds: u32) -> u32 {
 
     // dbg!(&inspected);
     inspected.sort();
-    inspected.iter().rev().take(2).product()
+    Ok(inspected.iter().rev().take(2).product())
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Items(Vec<u64>);
 
 impl Items {
@@ -79,19 +79,7 @@ impl Items {
     }
 }
 
-impl FromStr for Items {
-    type Err = Error<String>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match Items::parse(s).finish() {
-            Ok((_remaining, items)) => Ok(items),
-            Err(Error { input, code }) => Err(Error {
-                input: input.to_string(),
-                code,
-            }),
-        }
-    }
-}
+nomstr!(Items);
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Operation {
@@ -105,39 +93,26 @@ impl Operation {
         // Operation: new = old * 11
         let (i, _) = alt((take_until("*"), take_until("+")))(i)?;
         let (i, symbol) = alt((char('+'), char('*')))(i)?;
-        let (i, amount) = preceded(tag(" "), alphanumeric1)(i)?;
-        let op = match amount {
-            "old" => Operation::Square,
-            _ => match symbol {
-                '+' => Operation::Add(amount.parse::<u64>().unwrap()),
-                '*' => Operation::Multiply(amount.parse::<u64>().unwrap()),
-                _ => {
-                    return Err(nom::Err::Error(Error {
-                        input: "Invalid symbol",
-                        code: ErrorKind::Char,
-                    }))
-                }
-            },
+        let (i, _) = tag(" ")(i)?;
+        let (rest, amount) = alphanumeric1(i)?;
+        let op = match (amount, amount.parse::<u64>()) {
+            ("old", _) => Operation::Square,
+            (_, Ok(amount)) if symbol == '+' => Operation::Add(amount),
+            (_, Ok(amount)) if symbol == '*' => Operation::Multiply(amount),
+            _ => {
+                return Err(nom::Err::Error(Error {
+                    input: i,
+                    code: ErrorKind::Digit,
+                }))
+            }
         };
-        Ok((i, op))
+        Ok((rest, op))
     }
 }
 
-impl FromStr for Operation {
-    type Err = Error<String>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match Operation::parse(s).finish() {
-            Ok((_remaining, operation)) => Ok(operation),
-            Err(Error { input, code }) => Err(Error {
-                input: input.to_string(),
-                code,
-            }),
-        }
-    }
-}
+nomstr!(Operation);
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Test {
     amount: u64,
     truthy: u32,
@@ -166,21 +141,9 @@ impl Test {
     }
 }
 
-impl FromStr for Test {
-    type Err = Error<String>;
+nomstr!(Test);
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match Test::parse(s).finish() {
-            Ok((_remaining, test)) => Ok(test),
-            Err(Error { input, code }) => Err(Error {
-                input: input.to_string(),
-                code,
-            }),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Monkey {
     id: u8,
     items: Items,
@@ -216,18 +179,17 @@ impl Monkey {
     }
 
     pub fn worry_level(&self, item: u64, factor: u64) -> u64 {
-        let mut worry_level = match self.operation {
+        let worry_level = match self.operation {
             Operation::Add(v) => item + v,
             Operation::Multiply(v) => item * v,
             Operation::Square => item * item,
         };
-        worry_level = ((worry_level / factor) as f64).round() as u64;
-        worry_level
+        ((worry_level / factor) as f64).round() as u64
     }
 
     pub fn throw_item_to(&self, item: u64, factor: u64) -> (u64, usize) {
         let worry_level = self.worry_level(item, factor);
-        if worry_level % self.test.amount == 0 {
+        if worry_level.is_multiple_of(self.test.amount) {
             (worry_level, self.test.truthy as usize)
         } else {
             (worry_level, self.test.falsy as usize)
@@ -235,25 +197,13 @@ impl Monkey {
     }
 }
 
-impl FromStr for Monkey {
-    type Err = Error<String>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match Monkey::parse(s).finish() {
-            Ok((_remaining, monkey)) => Ok(monkey),
-            Err(Error { input, code }) => Err(Error {
-                input: input.to_string(),
-                code,
-            }),
-        }
-    }
-}
+nomstr!(Monkey);
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "Monkey 0:
+    const RAW_INPUT: &str = "Monkey 0:
     Starting items: 79, 98
     Operation: new = old * 19
     Test: divisible by 23
@@ -284,16 +234,16 @@ mod test {
 
     #[test]
     fn test_day11a_sample() {
-        let monkeys = parse_input_a(RAW_INPUT);
-        let inspected = process_input_a(&monkeys, 3, 20);
+        let monkeys = parse_input_a(RAW_INPUT).unwrap();
+        let inspected = process_input_a(&monkeys, 3, 20).unwrap();
         assert_eq!(inspected, 10605);
     }
 
     #[test]
     #[ignore]
     fn test_day11b_sample() {
-        let monkeys = parse_input_a(RAW_INPUT);
-        let inspected = process_input_a(&monkeys, 1, 10000);
+        let monkeys = parse_input_a(RAW_INPUT).unwrap();
+        let inspected = process_input_a(&monkeys, 1, 10000).unwrap();
         assert_eq!(inspected, 2713310158);
     }
 
@@ -315,6 +265,10 @@ mod test {
             "  Operation: new = old + 1".parse::<Operation>(),
             Ok(Operation::Add(1))
         );
+        assert_eq!(
+            format!("{:#}", "  Operation: new = old + x".parse::<Operation>().unwrap_err()),
+            "Digit failed at line 1, column 26\n    Operation: new = old + x\n                           ^"
+        );
     }
 
     #[test]
@@ -349,4 +303,17 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn test_day11_trailing_input() {
+        let input = RAW_INPUT.replacen(
+            "If false: throw to monkey 3",
+            "If false: throw to monkey 3 or 4",
+            1,
+        );
+        assert_eq!(
+            parse_input_a(&input).unwrap_err().to_string(),
+            "Syntax error: Unexpected trailing input at line 6, column 35\n        If false: throw to monkey 3 or 4\n                                    ^"
+        );
+    }
 }
@@ -1,28 +1,39 @@
 use itertools::Itertools;
+use log::{debug, trace};
 
+use crate::{
+    error::{AocError, Result},
+    grid::{Grid, Position},
+    solution,
+};
 use petgraph::{
     algo::dijkstra::dijkstra,
     // dot::{Config, Dot},
     graph::NodeIndex,
     Graph,
 };
-use std::fs;
-
-pub fn day12a() -> String {
-    let data = fs::read_to_string("assets/day12.txt").expect("Could not load file");
-    let squares = parse_input_a(&data);
-    let (nodes, graph, start, end) = build_graph(&squares);
-    let steps = process_input_a(&nodes, &graph, start, end);
-    steps.to_string()
-}
 
-pub fn day12b() -> String {
-    let data = fs::read_to_string("assets/day12.txt").expect("Could not load file");
-    let squares = parse_input_a(&data);
-    let (nodes, graph, _start, end) = build_graph(&squares);
-    let steps = process_input_b(&squares, &nodes, &graph, end);
-    steps.to_string()
-}
+solution!(Day12a, 12, A, |i| build_graph(&parse_input_a(i)?), |(
+    nodes,
+    graph,
+    start,
+    end,
+)| {
+    process_input_a(nodes, graph, *start, *end)
+});
+solution!(
+    Day12b,
+    12,
+    B,
+    |i| {
+        let squares = parse_input_a(i)?;
+        let (nodes, graph, _start, end) = build_graph(&squares)?;
+        Ok((squares, nodes, graph, end))
+    },
+    |(squares, nodes, graph, end)| process_input_b(squares, nodes, graph, *end)
+);
+
+type Graphed = (Grid<NodeIndex>, Graph<Square, u8>, Position, Position);
 
 #[derive(Debug, Clone, Copy)]
 pub struct Square {
@@ -30,205 +41,167 @@ pub struct Square {
     weight: u8,
 }
 
-pub fn parse_input_a(input: &str) -> Vec<Vec<Square>> {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| {
-                    let weight = match c {
-                        'S' => 'a' as u8,
-                        'E' => 'z' as u8,
-                        _ => c as u8,
-                    };
-                    Square { label: c, weight }
-                })
-                .collect_vec()
-        })
-        .collect_vec()
+impl Square {
+    /// The elevation, from `a` to `z`.
+    pub fn weight(&self) -> u8 {
+        self.weight
+    }
+}
+
+pub fn parse_input_a(input: &str) -> Result<Grid<Square>> {
+    Grid::parse_chars(input, |c| {
+        let weight = match c {
+            'S' => b'a',
+            'E' => b'z',
+            'a'..='z' => c as u8,
+            _ => return Err(format!("Invalid square '{c}'")),
+        };
+        Ok(Square { label: c, weight })
+    })
 }
 
-pub fn build_graph(
-    squares: &Vec<Vec<Square>>,
-) -> (
-    Vec<Vec<NodeIndex>>,
-    Graph<Square, u8>,
-    (usize, usize),
-    (usize, usize),
-) {
+/// Position of the square with a specific label.
+fn find_square(squares: &Grid<Square>, label: char) -> Result<Position> {
+    squares
+        .position(|s| s.label == label)
+        .ok_or_else(|| AocError::invalid_state(format!("There is no square '{label}'")))
+}
+
+pub fn build_graph(squares: &Grid<Square>) -> Result<Graphed> {
     let mut graph = Graph::<Square, u8>::new();
 
     // Create the nodes.
-    let row_count = squares.len();
-    let col_count = squares[0].len();
-    // dbg!(&row_count, &col_count);
-    let nodes = squares
-        .iter()
-        .map(|row| row.iter().map(|&s| graph.add_node(s)).collect_vec())
-        .collect_vec();
-    // for i in 0..row_count {
-    //     for j in 0..col_count {
-    //         let square = squares[i][j];
-    //         print!("{}", square.label);
-    //     }
-    //     println!();
-    // }
-
-    let mut start = (0, 0);
-    let mut end = (0, 0);
-    for (i, square_rows) in squares.iter().enumerate() {
-        for (j, square) in square_rows.iter().enumerate() {
-            // Lookup for the start and finish positions.
-            if square.label == 'S' {
-                start = (i, j);
-            }
-            if square.label == 'E' {
-                end = (i, j);
-            }
-
-            // Build the edges.
-            let neighbors: Vec<(i32, i32)> = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
-            for neighbor in neighbors {
-                let x = j as i32 + neighbor.0;
-                if x < 0 || x >= col_count as i32 {
-                    continue;
-                }
-                let y = i as i32 + neighbor.1;
-                if y < 0 || y >= row_count as i32 {
-                    continue;
-                }
-                // println!("[{i}, {j}] -> [{x}, {y}]");
-                let neighbor_square = squares[y as usize][x as usize];
-                if neighbor_square.weight <= square.weight
-                    || neighbor_square.weight == square.weight + 1
-                {
-                    graph.add_edge(nodes[i][j], nodes[y as usize][x as usize], 1);
-                }
+    let nodes = squares.map(|&s| graph.add_node(s));
+
+    // Lookup for the start and finish positions.
+    let start = find_square(squares, 'S')?;
+    let end = find_square(squares, 'E')?;
+    for (position, square) in squares.iter() {
+        // Build the edges.
+        for neighbor in squares.neighbours4(position) {
+            let neighbor_square = squares[neighbor];
+            if neighbor_square.weight <= square.weight
+                || neighbor_square.weight == square.weight + 1
+            {
+                graph.add_edge(nodes[position], nodes[neighbor], 1);
             }
         }
     }
 
     // Render the graph.
     // println!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
-    dbg!(&start, &end);
+    debug!("Start: {start}, end: {end}");
 
-    (nodes, graph, start, end)
+    Ok((nodes, graph, start, end))
 }
 
-pub fn build_graph_rev(
-    squares: &Vec<Vec<Square>>,
-) -> (
-    Vec<Vec<NodeIndex>>,
-    Graph<Square, u8>,
-    (usize, usize),
-    (usize, usize),
-) {
+pub fn build_graph_rev(squares: &Grid<Square>) -> Result<Graphed> {
     let mut graph = Graph::<Square, u8>::new();
 
     // Create the nodes.
-    let row_count = squares.len();
-    let col_count = squares[0].len();
-    // dbg!(&row_count, &col_count);
-    let nodes = squares
-        .iter()
-        .map(|row| row.iter().map(|&s| graph.add_node(s)).collect_vec())
-        .collect_vec();
-    // for i in 0..row_count {
-    //     for j in 0..col_count {
-    //         let square = squares[i][j];
-    //         print!("{}", square.label);
-    //     }
-    //     println!();
-    // }
-
-    let mut start = (0, 0);
-    let mut end = (0, 0);
-    for (i, square_rows) in squares.iter().enumerate() {
-        for (j, square) in square_rows.iter().enumerate() {
-            // Lookup for the start and finish positions.
-            if square.label == 'S' {
-                start = (i, j);
-            }
-            if square.label == 'E' {
-                end = (i, j);
-            }
-
-            // Build the edges.
-            let neighbors: Vec<(i32, i32)> = vec![(-1, 0), (1, 0), (0, -1), (0, 1)];
-            for neighbor in neighbors {
-                let x = j as i32 + neighbor.0;
-                if x < 0 || x >= col_count as i32 {
-                    continue;
-                }
-                let y = i as i32 + neighbor.1;
-                if y < 0 || y >= row_count as i32 {
-                    continue;
-                }
-                // println!("[{i}, {j}] -> [{x}, {y}]");
-                let neighbor_square = squares[y as usize][x as usize];
-                if neighbor_square.weight <= square.weight
-                    || neighbor_square.weight == square.weight + 1
-                {
-                    graph.add_edge(nodes[y as usize][x as usize], nodes[i][j], 1);
-                }
+    let nodes = squares.map(|&s| graph.add_node(s));
+
+    // Lookup for the start and finish positions.
+    let start = find_square(squares, 'S')?;
+    let end = find_square(squares, 'E')?;
+    for (position, square) in squares.iter() {
+        // Build the edges.
+        for neighbor in squares.neighbours4(position) {
+            let neighbor_square = squares[neighbor];
+            if neighbor_square.weight <= square.weight
+                || neighbor_square.weight == square.weight + 1
+            {
+                graph.add_edge(nodes[neighbor], nodes[position], 1);
             }
         }
     }
 
     // Render the graph.
     // println!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
-    dbg!(&start, &end);
+    debug!("Start: {start}, end: {end}");
 
-    (nodes, graph, start, end)
+    Ok((nodes, graph, start, end))
 }
 
 pub fn process_input_a(
-    nodes: &Vec<Vec<NodeIndex>>,
+    nodes: &Grid<NodeIndex>,
     graph: &Graph<Square, u8>,
-    start: (usize, usize),
-    end: (usize, usize),
-) -> usize {
+    start: Position,
+    end: Position,
+) -> Result<usize> {
     // Find the shortest path.
-    let end_node = nodes[end.0][end.1];
-    let shortest = dijkstra(&graph, nodes[start.0][start.1], Some(end_node), |_| 1);
+    let end_node = nodes[end];
+    let shortest = dijkstra(graph, nodes[start], Some(end_node), |_| 1);
     // dbg!(&shortest);
     // Why does it give me the value for all the nodes when I did specify the goal???
-    shortest[&end_node]
+    shortest
+        .get(&end_node)
+        .copied()
+        .ok_or_else(|| AocError::invalid_state("The best signal cannot be reached"))
+}
+
+/// The squares along a shortest path from `start` to `end`, both included.
+pub fn shortest_path(
+    squares: &Grid<Square>,
+    nodes: &Grid<NodeIndex>,
+    graph: &Graph<Square, u8>,
+    start: Position,
+    end: Position,
+) -> Result<Vec<Position>> {
+    let distances = dijkstra(graph, nodes[start], Some(nodes[end]), |_| 1);
+    let mut distance: usize = *distances
+        .get(&nodes[end])
+        .ok_or_else(|| AocError::invalid_state("The best signal cannot be reached"))?;
+
+    // Walk back from the end, through squares which are one step closer to the start.
+    let mut path = vec![end];
+    let mut position = end;
+    while distance > 0 {
+        distance -= 1;
+        position = squares
+            .neighbours4(position)
+            .find(|&p| {
+                distances.get(&nodes[p]) == Some(&distance)
+                    && graph.contains_edge(nodes[p], nodes[position])
+            })
+            .ok_or_else(|| AocError::invalid_state(format!("We lost the path at {position}")))?;
+        path.push(position);
+    }
+    path.reverse();
+    Ok(path)
 }
 
 pub fn process_input_b(
-    squares: &Vec<Vec<Square>>,
-    nodes: &Vec<Vec<NodeIndex>>,
+    squares: &Grid<Square>,
+    nodes: &Grid<NodeIndex>,
     graph: &Graph<Square, u8>,
-    start: (usize, usize),
-) -> usize {
-    let shortest = dijkstra(&graph, nodes[start.0][start.1], None, |_| 1);
-    dbg!(&shortest);
+    start: Position,
+) -> Result<usize> {
+    let shortest = dijkstra(graph, nodes[start], None, |_| 1);
+    trace!("{shortest:?}");
 
     // Find all 'a's.
     let mut all_as: Vec<NodeIndex> = Vec::new();
-    for (i, row) in squares.iter().enumerate() {
-        for (j, square) in row.iter().enumerate() {
-            if square.weight == 'a' as u8 {
-                println!("[{i},{j}]");
-                all_as.push(nodes[j][i]);
-            }
+    for (position, square) in squares.iter() {
+        if square.weight == b'a' {
+            trace!("Lowest square at {position}");
+            all_as.push(nodes[position]);
         }
     }
 
     let res = all_as
         .iter()
-        .map(|nodeindex| shortest[&nodeindex])
+        .map(|nodeindex| shortest.get(nodeindex))
         .collect_vec();
-    dbg!(&res);
-    0
+    debug!("{res:?}");
+    Ok(0)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "Sabqponm
+    const RAW_INPUT: &str = "Sabqponm
 abcryxxl
 accszExk
 acctuvwj
@@ -237,18 +210,23 @@ abdefghi
 
     #[test]
     fn test_day12a_sample() {
-        let squares = parse_input_a(RAW_INPUT);
-        let (nodes, graph, start, end) = build_graph(&squares);
-        let steps = process_input_a(&nodes, &graph, start, end);
+        let squares = parse_input_a(RAW_INPUT).unwrap();
+        let (nodes, graph, start, end) = build_graph(&squares).unwrap();
+        let steps = process_input_a(&nodes, &graph, start, end).unwrap();
         assert_eq!(steps, 31);
+
+        let path = shortest_path(&squares, &nodes, &graph, start, end).unwrap();
+        assert_eq!(path.len(), 32);
+        assert_eq!((path[0], path[31]), (start, end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
     }
 
     #[test]
     #[ignore]
     fn test_day12b_sample() {
-        let squares = parse_input_a(RAW_INPUT);
-        let (nodes, graph, _start, end) = build_graph_rev(&squares);
-        let steps = process_input_b(&squares, &nodes, &graph, end);
+        let squares = parse_input_a(RAW_INPUT).unwrap();
+        let (nodes, graph, _start, end) = build_graph_rev(&squares).unwrap();
+        let steps = process_input_b(&squares, &nodes, &graph, end).unwrap();
         assert_eq!(steps, 29);
     }
 }
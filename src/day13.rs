@@ -1,32 +1,27 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{newline, u32},
-    combinator::map,
+    character::complete::{multispace0, multispace1, newline, u32},
+    combinator::{all_consuming, map},
     error::Error,
-    multi::separated_list0,
-    sequence::{delimited, separated_pair},
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, separated_pair, terminated},
     Finish, IResult, Parser,
 };
 use std::{
     cmp::Ordering::{Equal, Greater, Less},
-    fs,
     str::FromStr,
 };
 
-pub fn day13a() -> String {
-    let data = fs::read_to_string("assets/day13.txt").expect("Could not load file");
-    let pairs = parse_input_a(&data);
-    let sum = process_input_a(&pairs);
-    sum.to_string()
-}
+use crate::{
+    error::{AocError, Result},
+    solution,
+};
 
-pub fn day13b() -> String {
-    let data = fs::read_to_string("assets/day13.txt").expect("Could not load file");
-    let pairs = parse_input_a(&data);
-    let sum = process_input_b(&pairs);
-    sum.to_string()
-}
+solution!(Day13a, 13, A, parse_input_a, |pairs| Ok(process_input_a(
+    pairs
+)));
+solution!(Day13b, 13, B, parse_input_a, |pairs| process_input_b(pairs));
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Pair {
@@ -104,7 +99,7 @@ impl Ord for Packet {
         match (self, other) {
             (Packet::List(a), Packet::List(b)) => a.cmp(b),
             (Packet::List(a), Packet::Number(b)) => a.cmp(&vec![Packet::Number(*b)]),
-            (Packet::Number(a), Packet::List(b)) => vec![Packet::Number(*a)].cmp(&b),
+            (Packet::Number(a), Packet::List(b)) => vec![Packet::Number(*a)].cmp(b),
             (Packet::Number(a), Packet::Number(b)) => a.cmp(b),
         }
     }
@@ -114,18 +109,20 @@ impl Packet {
     pub fn parse(i: &str) -> IResult<&str, Packet> {
         alt((
             delimited(tag("["), separated_list0(tag(","), Packet::parse), tag("]"))
-                .map(|vec| Packet::List(vec)),
-            u32.map(|num| Packet::Number(num)),
+                .map(Packet::List),
+            u32.map(Packet::Number),
         ))(i)
     }
 }
 
-pub fn parse_input_a(input: &str) -> Vec<Pair> {
-    input
-        .split("\n\n")
-        .map(String::from)
-        .map(|l| l.parse::<Pair>().unwrap())
-        .collect()
+pub fn parse_input_a(input: &str) -> Result<Vec<Pair>> {
+    all_consuming(terminated(
+        separated_list1(multispace1, Pair::parse),
+        multispace0,
+    ))(input)
+    .finish()
+    .map(|(_, pairs)| pairs)
+    .map_err(|e| AocError::from_nom(input, e))
 }
 
 pub fn process_input_a(pairs: &[Pair]) -> usize {
@@ -142,7 +139,7 @@ pub fn process_input_a(pairs: &[Pair]) -> usize {
         .sum::<usize>()
 }
 
-pub fn process_input_b(pairs: &[Pair]) -> usize {
+pub fn process_input_b(pairs: &[Pair]) -> Result<usize> {
     let mut ordered_packets: Vec<Packet> = Vec::new();
     for pair in pairs {
         ordered_packets.push(pair.left.clone());
@@ -153,22 +150,22 @@ pub fn process_input_b(pairs: &[Pair]) -> usize {
     ordered_packets.push(dp2);
     ordered_packets.push(dp6);
     ordered_packets.sort();
-    let dp2_pos = ordered_packets
-        .iter()
-        .position(|p| *p == Packet::List(vec![Packet::Number(2)]))
-        .unwrap();
-    let dp6_pos = ordered_packets
-        .iter()
-        .position(|p| *p == Packet::List(vec![Packet::Number(6)]))
-        .unwrap();
-    (dp2_pos + 1) * (dp6_pos + 1)
+    let position = |divider: &Packet| {
+        ordered_packets
+            .iter()
+            .position(|p| p == divider)
+            .ok_or_else(|| AocError::invalid_state(format!("The divider {divider:?} was lost")))
+    };
+    let dp2_pos = position(&Packet::List(vec![Packet::Number(2)]))?;
+    let dp6_pos = position(&Packet::List(vec![Packet::Number(6)]))?;
+    Ok((dp2_pos + 1) * (dp6_pos + 1))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "[1,1,3,1,1]
+    const RAW_INPUT: &str = "[1,1,3,1,1]
 [1,1,5,1,1]
 
 [[1],[2,3,4]]
@@ -195,15 +192,15 @@ mod test {
 
     #[test]
     fn test_day13a_sample() {
-        let pairs = parse_input_a(RAW_INPUT);
+        let pairs = parse_input_a(RAW_INPUT).unwrap();
         let sum = process_input_a(&pairs);
         assert_eq!(sum, 13);
     }
 
     #[test]
     fn test_day13b_sample() {
-        let pairs = parse_input_a(RAW_INPUT);
-        let decoder_key = process_input_b(&pairs);
+        let pairs = parse_input_a(RAW_INPUT).unwrap();
+        let decoder_key = process_input_b(&pairs).unwrap();
         assert_eq!(decoder_key, 140);
     }
 }
@@ -1,73 +1,53 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-    str::FromStr,
-};
+use std::{collections::HashSet, fmt::Display};
 
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
     combinator::map,
-    error::Error,
     multi::separated_list1,
     sequence::separated_pair,
-    Finish, IResult,
+    IResult,
 };
 
-use crate::nomstr;
-
-pub fn day14a() -> String {
-    let data = fs::read_to_string("assets/day14.txt").expect("Could not load file");
-    let mut cave = data.parse::<Cave>().unwrap();
-    let resting_sand = process_input_a(&mut cave);
-    resting_sand.to_string()
-}
-
-pub fn day14b() -> String {
-    let data = fs::read_to_string("assets/day14.txt").expect("Could not load file");
-    let mut cave = data.parse::<Cave>().unwrap();
-    let resting_sand = process_input_b(&mut cave);
-    resting_sand.to_string()
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct Coordinate {
-    x: u32,
-    y: u32,
-}
-
-impl Coordinate {
-    pub fn parse(i: &str) -> IResult<&str, Coordinate> {
-        map(
-            separated_pair(complete::u32, complete::char(','), complete::u32),
-            |(x, y)| Coordinate { x, y },
-        )(i)
-    }
-
-    pub fn down(&mut self) {
-        self.y += 1;
-    }
-
-    pub fn down_left(&mut self) {
-        self.x -= 1;
-        self.y += 1;
-    }
+use crate::{
+    error::{AocError, Result},
+    geom::Point2,
+    grid::SparseGrid,
+    nomstr, solution,
+    visualize::{Frame, NullVisualizer, Visualizer},
+};
 
-    pub fn down_right(&mut self) {
-        self.x += 1;
-        self.y += 1;
-    }
+solution!(Day14a, 14, A, parse_input_a, |cave| process_input_a(
+    cave,
+    &mut NullVisualizer
+));
+solution!(Day14b, 14, B, parse_input_a, |cave| process_input_b(
+    cave,
+    &mut NullVisualizer
+));
+
+pub type Coordinate = Point2<i64>;
+
+/// Where the sand comes from.
+const SOURCE: Coordinate = Point2::new(500, 0);
+const DOWN: Coordinate = Point2::new(0, 1);
+const DOWN_LEFT: Coordinate = Point2::new(-1, 1);
+const DOWN_RIGHT: Coordinate = Point2::new(1, 1);
+
+pub fn parse_coordinate(i: &str) -> IResult<&str, Coordinate> {
+    map(
+        separated_pair(complete::i64, complete::char(','), complete::i64),
+        |(x, y)| Coordinate { x, y },
+    )(i)
 }
 
-nomstr!(Coordinate);
-
 #[derive(Debug, PartialEq, Eq)]
 pub struct Wall(HashSet<Coordinate>);
 
 impl Wall {
     pub fn parse(i: &str) -> IResult<&str, Wall> {
-        let (i, coordinates) = separated_list1(tag(" -> "), Coordinate::parse)(i)?;
+        let (i, coordinates) = separated_list1(tag(" -> "), parse_coordinate)(i)?;
         let rocks = coordinates
             .iter()
             .tuple_windows()
@@ -89,151 +69,183 @@ impl Wall {
     }
 
     pub fn add(&mut self, rock: &Coordinate) {
-        self.0.insert(rock.clone());
+        self.0.insert(*rock);
     }
 }
 
 nomstr!(Wall);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Tile {
     Rock,
     Sand,
 }
 
-#[derive(Debug)]
-pub struct Cave(HashMap<Coordinate, Tile>);
+#[derive(Debug, Clone)]
+pub struct Cave(SparseGrid<Tile>);
 
 impl Cave {
     pub fn parse(i: &str) -> IResult<&str, Cave> {
         let (i, walls) = separated_list1(line_ending, Wall::parse)(i)?;
-        let mut cave = Cave(HashMap::new());
+        let mut cave = Cave(SparseGrid::new());
         for wall in walls {
             for coordinate in wall.0 {
-                cave.0.insert(coordinate, Tile::Rock);
+                cave.add(coordinate, Tile::Rock);
             }
         }
         Ok((i, cave))
     }
 
-    pub fn lowest_point(&self) -> Coordinate {
-        let mut coordinates = self.0.keys().collect_vec();
-        coordinates.sort_by(|c1, c2| c1.y.cmp(&c2.y));
-        **coordinates.last().unwrap()
+    /// Depth of the lowest rock or sand unit.
+    pub fn lowest_point(&self) -> Result<i64> {
+        self.0
+            .bounds()
+            .map(|(_, max)| max.y)
+            .ok_or_else(|| AocError::invalid_state("There are no rocks in the cave"))
     }
 
     pub fn add(&mut self, coordinate: Coordinate, tile: Tile) {
         self.0.insert(coordinate, tile);
     }
+
+    pub fn get(&self, coordinate: Coordinate) -> Option<&Tile> {
+        self.0.get(coordinate)
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cave = self.0.render('.', |tile| match tile {
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        });
+        write!(f, "{cave}")
+    }
 }
 
 nomstr!(Cave);
 
-pub fn process_input_a(cave: &mut Cave) -> u32 {
-    let lowest_point = cave.lowest_point();
+pub fn parse_input_a(input: &str) -> Result<Cave> {
+    Ok(input.parse::<Cave>()?)
+}
+
+pub fn process_input_a(cave: &mut Cave, visualizer: &mut dyn Visualizer) -> Result<u32> {
+    let lowest_point = cave.lowest_point()?;
     let mut resting_sand = 0;
-    let mut current_sand = Coordinate { x: 500, y: 0 };
-    while current_sand.y < lowest_point.y {
-        let (d, dl, dr) = get_next_row(&cave, current_sand);
+    let mut current_sand = SOURCE;
+    while current_sand.y < lowest_point {
+        let (d, dl, dr) = get_next_row(cave, current_sand);
         // Try to go down.
         if d.is_none() {
-            current_sand.down();
+            current_sand += DOWN;
             continue;
         }
 
         // Try to go down-left.
         if dl.is_none() {
-            current_sand.down_left();
+            current_sand += DOWN_LEFT;
             continue;
         }
 
         // Try to go down-right.
         if dr.is_none() {
-            current_sand.down_right();
+            current_sand += DOWN_RIGHT;
             continue;
         }
 
         // If frozen, reset the current sand.
-        cave.add(current_sand.clone(), Tile::Sand);
+        cave.add(current_sand, Tile::Sand);
         resting_sand += 1;
-        current_sand = Coordinate { x: 500, y: 0 };
+        visualizer.draw(&|| Frame::new(format!("Sand unit {resting_sand}"), cave.to_string()));
+        current_sand = SOURCE;
     }
 
-    resting_sand
+    Ok(resting_sand)
 }
 
-pub fn process_input_b(cave: &mut Cave) -> u32 {
-    let mut lowest_point = cave.lowest_point();
-    lowest_point.y += 2;
+pub fn process_input_b(cave: &mut Cave, visualizer: &mut dyn Visualizer) -> Result<u32> {
+    let lowest_point = cave.lowest_point()? + 2;
     let mut resting_sand = 0;
-    let mut current_sand = Coordinate { x: 500, y: 0 };
+    let mut current_sand = SOURCE;
 
-    while cave.0.get(&Coordinate { x: 500, y: 0 }).is_none() {
-        if current_sand.y == lowest_point.y - 1 {
-            cave.add(current_sand.clone(), Tile::Sand);
+    while cave.get(SOURCE).is_none() {
+        if current_sand.y == lowest_point - 1 {
+            cave.add(current_sand, Tile::Sand);
             resting_sand += 1;
-            current_sand = Coordinate { x: 500, y: 0 };
+            visualizer.draw(&|| Frame::new(format!("Sand unit {resting_sand}"), cave.to_string()));
+            current_sand = SOURCE;
         }
-        let (d, dl, dr) = get_next_row(&cave, current_sand);
+        let (d, dl, dr) = get_next_row(cave, current_sand);
         // Try to go down.
         if d.is_none() {
-            current_sand.down();
+            current_sand += DOWN;
             continue;
         }
 
         // Try to go down-left.
         if dl.is_none() {
-            current_sand.down_left();
+            current_sand += DOWN_LEFT;
             continue;
         }
 
         // Try to go down-right.
         if dr.is_none() {
-            current_sand.down_right();
+            current_sand += DOWN_RIGHT;
             continue;
         }
 
         // If frozen, reset the current sand.
-        cave.add(current_sand.clone(), Tile::Sand);
+        cave.add(current_sand, Tile::Sand);
         resting_sand += 1;
-        current_sand = Coordinate { x: 500, y: 0 };
+        visualizer.draw(&|| Frame::new(format!("Sand unit {resting_sand}"), cave.to_string()));
+        current_sand = SOURCE;
     }
 
-    resting_sand
+    Ok(resting_sand)
 }
 
 pub fn get_next_row(cave: &Cave, c: Coordinate) -> (Option<&Tile>, Option<&Tile>, Option<&Tile>) {
-    let d = cave.0.get(&Coordinate { x: c.x, y: c.y + 1 });
-    let dl = cave.0.get(&Coordinate {
-        x: c.x - 1,
-        y: c.y + 1,
-    });
-    let dr = cave.0.get(&Coordinate {
-        x: c.x + 1,
-        y: c.y + 1,
-    });
+    let d = cave.get(c + DOWN);
+    let dl = cave.get(c + DOWN_LEFT);
+    let dr = cave.get(c + DOWN_RIGHT);
     (d, dl, dr)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::visualize::Recorder;
 
-    const RAW_INPUT: &'static str = "498,4 -> 498,6 -> 496,6
+    const RAW_INPUT: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9
 ";
 
     #[test]
     fn test_day14a_sample() {
         let mut cave = RAW_INPUT.parse::<Cave>().unwrap();
-        let resting_sand = process_input_a(&mut cave);
+        let mut recorder = Recorder::default();
+        let resting_sand = process_input_a(&mut cave, &mut recorder).unwrap();
         assert_eq!(resting_sand, 24);
+        assert_eq!(
+            cave.to_string(),
+            "......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.
+"
+        );
+        assert_eq!(recorder.frames.len(), 24);
+        assert_eq!(recorder.frames[23].picture, cave.to_string());
     }
 
     #[test]
     fn test_day14b_sample() {
         let mut cave = RAW_INPUT.parse::<Cave>().unwrap();
-        let resting_sand = process_input_b(&mut cave);
+        let resting_sand = process_input_b(&mut cave, &mut NullVisualizer).unwrap();
         assert_eq!(resting_sand, 93);
     }
 
@@ -1,49 +1,50 @@
-use std::{collections::HashSet, fs, str::FromStr};
+use std::{collections::HashSet, str::FromStr};
 
-use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete::{self},
     combinator::map,
-    error::Error,
     sequence::{preceded, separated_pair},
-    Finish, IResult,
+    IResult,
 };
+use serde::Deserialize;
 
-use crate::nomstr;
-
-pub fn day15a() -> String {
-    let data = fs::read_to_string("assets/day15.txt").expect("Could not load file");
-    let sensors = parse_input_a(&data);
-    let count = process_input_a(&sensors, 2000000);
-    count.to_string()
-}
+use crate::{
+    error::{parse_lines, Result},
+    geom::Point2,
+    nomstr, solution,
+};
 
-pub fn day15b() -> String {
-    let data = fs::read_to_string("assets/day15.txt").expect("Could not load file");
-    data
-}
+solution!(Day15a, 15, A, params, parse_input_a, |sensors, params| Ok(
+    process_input_a(sensors, params.day15.row)
+));
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct Coordinate {
-    x: i64,
-    y: i64,
+/// Where the beacons are looked for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scan {
+    /// The row where the positions which cannot contain a beacon are counted.
+    pub row: i64,
 }
 
-impl Coordinate {
-    pub fn parse(i: &str) -> IResult<&str, Coordinate> {
-        map(
-            separated_pair(
-                preceded(tag("x="), complete::i64),
-                tag(", "),
-                preceded(tag("y="), complete::i64),
-            ),
-            |(x, y)| Coordinate { x, y },
-        )(i)
+impl Default for Scan {
+    fn default() -> Self {
+        Scan { row: 2_000_000 }
     }
 }
 
-nomstr!(Coordinate);
+pub type Coordinate = Point2<i64>;
+
+pub fn parse_coordinate(i: &str) -> IResult<&str, Coordinate> {
+    map(
+        separated_pair(
+            preceded(tag("x="), complete::i64),
+            tag(", "),
+            preceded(tag("y="), complete::i64),
+        ),
+        |(x, y)| Coordinate { x, y },
+    )(i)
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Sensor {
@@ -53,16 +54,14 @@ pub struct Sensor {
 
 impl Sensor {
     pub fn parse(i: &str) -> IResult<&str, Sensor> {
-        let (i, position) = preceded(tag("Sensor at "), Coordinate::parse)(i)?;
-        let (i, beacon) = preceded(tag(": closest beacon is at "), Coordinate::parse)(i)?;
+        let (i, position) = preceded(tag("Sensor at "), parse_coordinate)(i)?;
+        let (i, beacon) = preceded(tag(": closest beacon is at "), parse_coordinate)(i)?;
         Ok((i, Sensor { position, beacon }))
     }
 
     // Computes the manhattan distance between the sensor and its beacon.
-    //
-    // https://en.wikipedia.org/wiki/Taxicab_geometry
     pub fn manhattan_distance(&self) -> i64 {
-        (self.beacon.x - self.position.x).abs() + (self.beacon.y - self.position.y).abs()
+        self.position.manhattan(&self.beacon)
     }
 
     pub fn no_beacon_zone(&self) -> HashSet<Coordinate> {
@@ -91,10 +90,8 @@ impl Sensor {
 
 nomstr!(Sensor);
 
-pub fn parse_input_a(i: &str) -> Vec<Sensor> {
-    i.lines()
-        .map(|l| l.parse::<Sensor>().unwrap())
-        .collect_vec()
+pub fn parse_input_a(i: &str) -> Result<Vec<Sensor>> {
+    parse_lines(i, Sensor::from_str)
 }
 
 pub fn process_input_a(sensors: &[Sensor], row: i64) -> usize {
@@ -135,7 +132,7 @@ pub fn process_input_b(_sensors: &[Sensor], _boundary: i64) -> usize {
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+    const RAW_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
 Sensor at x=12, y=14: closest beacon is at x=10, y=16
@@ -153,7 +150,7 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3
 
     #[test]
     fn test_day15a_sample() {
-        let sensors = parse_input_a(RAW_INPUT);
+        let sensors = parse_input_a(RAW_INPUT).unwrap();
         let count = process_input_a(&sensors, 10);
         assert_eq!(count, 26);
     }
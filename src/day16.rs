@@ -1,15 +1,17 @@
-use crate::nomstr;
-use itertools::Itertools;
+use crate::{
+    error::{parse_lines, AocError, Result},
+    nomstr,
+};
+use log::trace;
 use nom::{
     bytes::complete::{tag, take_while},
     character::{
         complete::{self, alpha1},
         is_alphabetic, is_space,
     },
-    error::Error,
     multi::separated_list1,
     sequence::preceded,
-    Finish, IResult,
+    IResult,
 };
 use petgraph::{
     // algo::dijkstra::dijkstra,
@@ -17,17 +19,7 @@ use petgraph::{
     graph::NodeIndex,
     Graph,
 };
-use std::{collections::HashMap, fs, iter::zip, str::FromStr};
-
-pub fn day16a() -> String {
-    let data = fs::read_to_string("assets/day16.txt").expect("Could not load file");
-    data
-}
-
-pub fn day16b() -> String {
-    let data = fs::read_to_string("assets/day16.txt").expect("Could not load file");
-    data
-}
+use std::{collections::HashMap, iter::zip, str::FromStr};
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Valve {
@@ -66,11 +58,11 @@ impl Valve {
 }
 nomstr!(Valve);
 
-pub fn parse_input_a(i: &str) -> Vec<Valve> {
-    i.lines().map(|l| Valve::from_str(l).unwrap()).collect_vec()
+pub fn parse_input_a(i: &str) -> Result<Vec<Valve>> {
+    parse_lines(i, Valve::from_str)
 }
 
-pub fn build_graph(valves: &[Valve]) -> Graph<(&str, u32), u32> {
+pub fn build_graph(valves: &[Valve]) -> Result<Graph<(&str, u32), u32>> {
     let mut graph = Graph::<(&str, u32), u32>::new();
 
     // Build nodes.
@@ -84,28 +76,30 @@ pub fn build_graph(valves: &[Valve]) -> Graph<(&str, u32), u32> {
         .collect::<HashMap<String, NodeIndex>>();
 
     // Build edges.
+    let index = |label: &String| {
+        zipped
+            .get(label)
+            .copied()
+            .ok_or_else(|| AocError::invalid_state(format!("Unknown valve {label}")))
+    };
     for valve in valves {
         for tunnel in &valve.tunnels {
-            graph.add_edge(
-                *zipped.get(&valve.label).unwrap(),
-                *zipped.get(tunnel).unwrap(),
-                valve.flow_rate,
-            );
+            graph.add_edge(index(&valve.label)?, index(tunnel)?, valve.flow_rate);
         }
     }
 
     // Render the graph.
-    println!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
+    trace!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
 
     //
-    graph
+    Ok(graph)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+    const RAW_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
 Valve BB has flow rate=13; tunnels lead to valves CC, AA
 Valve CC has flow rate=2; tunnels lead to valves DD, BB
 Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
@@ -119,8 +113,22 @@ Valve JJ has flow rate=21; tunnel leads to valve II
 
     #[test]
     fn test_day16a_sample() {
-        let valves = parse_input_a(RAW_INPUT);
-        let _graph = build_graph(&valves);
+        let valves = parse_input_a(RAW_INPUT).unwrap();
+        let _graph = build_graph(&valves).unwrap();
+    }
+
+    #[test]
+    fn test_day16_invalid_input() {
+        let error = parse_input_a("Valve AA has flow rate=0; tunnel leads to valve BB\nValve BB\n")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Syntax error: Tag failed at line 2, column 9\n  Valve BB\n          ^"
+        );
+
+        let valves = parse_input_a("Valve AA has flow rate=0; tunnel leads to valve BB\n").unwrap();
+        let error = build_graph(&valves).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid puzzle state: Unknown valve BB");
     }
 
     #[test]
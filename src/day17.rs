@@ -1,69 +1,51 @@
-use itertools::Itertools;
-use nom::{branch::alt, bytes::complete::tag, combinator::value, multi::many1, IResult};
-use std::{
-    cmp::max,
-    collections::{HashMap, HashSet},
-    fmt::Display,
-    fs,
-    ops::Add,
+use log::debug;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::multispace0,
+    combinator::{all_consuming, value},
+    multi::many1,
+    sequence::terminated,
+    Finish, IResult,
+};
+use serde::Deserialize;
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{
+    error::{AocError, Result},
+    geom::{Direction, Point2},
+    solution,
+    visualize::{Frame, NullVisualizer, Visualizer},
 };
 
-pub fn day17a() -> String {
-    let data = fs::read_to_string("assets/day17.txt").expect("Could not load file");
-    let rock_count = process_input_a(&data, 2022);
-    rock_count.to_string()
-    // 3266 is too high
-}
-
-pub fn day17b() -> String {
-    let data = fs::read_to_string("assets/day17.txt").expect("Could not load file");
-    data
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
-pub struct Coordinate {
-    x: i32,
-    y: i32,
-}
-
-impl Add for Coordinate {
-    type Output = Self;
+solution!(Day17a, 17, A, params, parse_input_a, |motions, params| {
+    process_input_a(motions, params.day17.rocks, &mut NullVisualizer)
+});
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
+/// The number of rocks falling into the chamber.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tower {
+    pub rocks: usize,
 }
 
-impl Display for Coordinate {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{},{}]", self.x, self.y)
+impl Default for Tower {
+    fn default() -> Self {
+        Tower { rocks: 2022 }
     }
 }
 
-impl Coordinate {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
+/// Number of rows of the chamber shown in a frame, from the top of the tower.
+const FRAME_ROWS: i32 = 30;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub enum Direction {
-    Down,
-    Left,
-    Right,
-    Up,
-}
+/// A position in the chamber, `y` growing upwards.
+pub type Coordinate = Point2<i32>;
 
-impl Direction {
-    pub fn parse(i: &str) -> IResult<&str, Self> {
-        alt((
-            value(Direction::Left, tag("<")),
-            value(Direction::Right, tag(">")),
-        ))(i)
-    }
+pub fn parse_jet(i: &str) -> IResult<&str, Direction> {
+    alt((
+        value(Direction::Left, tag("<")),
+        value(Direction::Right, tag(">")),
+    ))(i)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -84,12 +66,8 @@ pub struct Rock {
 
 impl Display for Rock {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut formation = self
-            .formation
-            .iter()
-            .map(|&c| c)
-            .collect::<Vec<Coordinate>>();
-        formation.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+        let mut formation = self.formation.iter().copied().collect::<Vec<Coordinate>>();
+        formation.sort_by_key(|c| c.x);
         let results = formation
             .iter()
             .map(|c| format!("{c}"))
@@ -220,9 +198,9 @@ impl Display for Chamber {
         let mut bottom_line = self
             .bottom_line
             .iter()
-            .map(|&c| c)
+            .copied()
             .collect::<Vec<Coordinate>>();
-        bottom_line.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+        bottom_line.sort_by_key(|c| c.x);
         let results = bottom_line
             .iter()
             .map(|c| format!("{c}"))
@@ -232,6 +210,12 @@ impl Display for Chamber {
     }
 }
 
+impl Default for Chamber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Chamber {
     pub fn new() -> Self {
         let mut bottom_line = HashSet::new();
@@ -282,15 +266,62 @@ impl Chamber {
             self.bottom_line.insert(*rock_part);
         }
     }
+
+    /// Draw the top `rows` rows of the chamber like the puzzle does, the parts of `rock`
+    /// as `@`.
+    pub fn render(&self, rock: Option<&Rock>, rows: i32) -> String {
+        let top = rock
+            .and_then(|r| r.formation.iter().map(|c| c.y).max())
+            .unwrap_or_default()
+            .max(self.highest_rock());
+        let mut picture = String::new();
+        for y in ((top - rows + 1).max(1)..=top).rev() {
+            picture.push('|');
+            for x in 0..7 {
+                let c = Coordinate::new(x, y);
+                picture.push(if rock.is_some_and(|r| r.formation.contains(&c)) {
+                    '@'
+                } else if self.bottom_line.contains(&c) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            picture.push_str("|\n");
+        }
+        if top < rows {
+            picture.push_str("+-------+\n");
+        }
+        picture
+    }
 }
 
 pub fn parse_motions(i: &str) -> IResult<&str, Vec<Direction>> {
-    many1(Direction::parse)(i)
+    many1(parse_jet)(i)
+}
+
+pub fn parse_input_a(i: &str) -> Result<Vec<Direction>> {
+    all_consuming(terminated(parse_motions, multispace0))(i)
+        .finish()
+        .map(|(_, motions)| motions)
+        .map_err(|e| AocError::from_nom(i, e))
 }
 
-pub fn process_input_a(i: &str, limit: usize) -> i32 {
+pub fn process_input_a(
+    motions: &[Direction],
+    limit: usize,
+    visualizer: &mut dyn Visualizer,
+) -> Result<i32> {
+    Ok(build_tower(motions, limit, visualizer)?.highest_rock())
+}
+
+/// Let `limit` rocks fall into the chamber, pushed by the jets.
+pub fn build_tower(
+    motions: &[Direction],
+    limit: usize,
+    visualizer: &mut dyn Visualizer,
+) -> Result<Chamber> {
     let mut chamber = Chamber::new();
-    let (_, motions) = parse_motions(i).unwrap();
     let rocks: Vec<Rock> = vec![
         Rock::new(Shape::Minus, None),
         Rock::new(Shape::Plus, None),
@@ -307,7 +338,7 @@ pub fn process_input_a(i: &str, limit: usize) -> i32 {
     for rock in rocks.iter().cycle() {
         // Stop when hitting the limit.
         if rock_count >= limit {
-            return chamber.highest_rock();
+            return Ok(chamber);
         }
 
         // Bring the rock in.
@@ -315,10 +346,13 @@ pub fn process_input_a(i: &str, limit: usize) -> i32 {
         let mut r = rock.clone();
         r.offset(&Coordinate::new(2, chamber.highest_rock() + 1 + 3));
 
-        while let Some(motion) = motion_iter.next() {
-            // Try to shift the rock laterally.
-            if r.can_shift(&motion) {
+        for motion in motion_iter.by_ref() {
+            // Try to shift the rock laterally, unless it would bump into the settled rocks.
+            if r.can_shift(motion) {
                 r.shift(motion);
+                if r.formation.intersection(&chamber.bottom_line).count() > 0 {
+                    r.shift(&motion.reverse());
+                }
             }
 
             // Move it down.
@@ -326,56 +360,73 @@ pub fn process_input_a(i: &str, limit: usize) -> i32 {
 
             // Sanity checks for debugging.
             if r.formation.iter().any(|c| c.y < 0) {
-                dbg!(&rock_count);
-                dbg!(&r);
-                dbg!(&chamber);
-                panic!("Cannot go that low!");
+                debug!("Rock {rock_count}: {r:?}\n{chamber:?}");
+                return Err(AocError::invalid_state("Cannot go that low!"));
             }
             if r.formation.iter().any(|c| c.x < 0 || c.x > 6) {
-                dbg!(&r);
-                dbg!(&chamber);
-                panic!("Cannot shift that far!");
+                debug!("Rock {rock_count}: {r:?}\n{chamber:?}");
+                return Err(AocError::invalid_state("Cannot shift that far!"));
             }
 
-            // println!("{r}");
-
             // Does it intersect with the bottom?
             if r.formation.intersection(&chamber.bottom_line).count() > 0 {
-                // println!("Intersection!");
                 // Then move it back up.
                 r.shift(&Direction::Up);
-                // println!("{r}");
 
                 // Update chamber's bottom line
                 chamber.update(&r);
-                // println!("Bottom line: {chamber}");
+                visualizer.draw(&|| {
+                    Frame::new(
+                        format!("Rock {rock_count}"),
+                        chamber.render(Some(&r), FRAME_ROWS),
+                    )
+                });
 
                 // Move to the next rock.
                 break;
             }
 
             if r.formation.iter().any(|c| c.y <= chamber.lowest_rock()) {
-                dbg!(&chamber.highest_rock());
-                dbg!(&rock_count);
-                dbg!(&r);
-                dbg!(&chamber);
-                panic!("We missed an intersection!");
+                debug!(
+                    "Rock {rock_count}, highest rock {}: {r:?}\n{chamber:?}",
+                    chamber.highest_rock()
+                );
+                return Err(AocError::invalid_state("We missed an intersection!"));
             }
         }
     }
 
-    chamber.highest_rock()
+    Ok(chamber)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::visualize::Recorder;
+
+    const RAW_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
 
-    const RAW_INPUT: &'static str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+    #[test]
+    fn test_day17_frames() {
+        let motions = parse_input_a(RAW_INPUT).unwrap();
+        let mut recorder = Recorder::default();
+        process_input_a(&motions, 2, &mut recorder).unwrap();
+        assert_eq!(
+            recorder.frames,
+            [
+                Frame::new("Rock 1", "|..@@@@.|\n+-------+\n"),
+                Frame::new(
+                    "Rock 2",
+                    "|...@...|\n|..@@@..|\n|...@...|\n|..####.|\n+-------+\n"
+                ),
+            ]
+        );
+    }
 
     #[test]
     fn test_day17a_sample() {
-        let rock_count = process_input_a(RAW_INPUT, 2022);
+        let motions = parse_input_a(RAW_INPUT).unwrap();
+        let rock_count = process_input_a(&motions, 2022, &mut NullVisualizer).unwrap();
         assert_eq!(rock_count, 3068);
     }
 
@@ -405,7 +456,7 @@ mod test {
     fn test_rock_can_shift() {
         let mut rock = Rock::new(Shape::Minus, Some(Coordinate::new(2, 2)));
         let can_shift = rock.can_shift(&Direction::Left);
-        assert_eq!(can_shift, true);
+        assert!(can_shift);
         rock.shift(&Direction::Left);
         let mut formation = HashSet::new();
         formation.insert(Coordinate::new(1, 2));
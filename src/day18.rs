@@ -1,106 +1,45 @@
-use crate::nomstr;
+use crate::{
+    error::{parse_all, parse_lines, Result},
+    geom::Point3,
+    solution,
+};
 use nom::{
-    bytes::complete::tag, character::complete, combinator::map, error::Error,
-    multi::separated_list1, Finish, IResult,
+    bytes::complete::tag,
+    character::complete,
+    combinator::map,
+    sequence::{preceded, tuple},
+    IResult,
 };
-use std::{collections::HashSet, fs, ops::Add, str::FromStr};
-
-pub fn day18a() -> String {
-    let data = fs::read_to_string("assets/day18.txt").expect("Could not load file");
-    let cubes = parse_input_a(&data);
-    let not_connected = process_input_a(&cubes);
-    not_connected.to_string()
-}
-
-pub fn day18b() -> String {
-    let data = fs::read_to_string("assets/day18.txt").expect("Could not load file");
-    data
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
-pub struct Coordinate {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Add for Coordinate {
-    type Output = Self;
+use std::collections::HashSet;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.y + rhs.z,
-        }
-    }
-}
+solution!(Day18a, 18, A, parse_input_a, |cubes| Ok(process_input_a(
+    cubes
+)));
 
-impl Coordinate {
-    pub fn parse(i: &str) -> IResult<&str, Coordinate> {
-        map(separated_list1(tag(","), complete::i32), |v| Coordinate {
-            x: v[0],
-            y: v[1],
-            z: v[2],
-        })(i)
-    }
+pub type Coordinate = Point3<i32>;
 
-    pub fn neighbors(&self) -> Vec<Coordinate> {
-        let x = self.x;
-        let y = self.y;
-        let z = self.z;
-        vec![
-            Coordinate {
-                x: self.x - 1,
-                y,
-                z,
-            },
-            Coordinate {
-                x: self.x + 1,
-                y,
-                z,
-            },
-            Coordinate {
-                x,
-                y: self.y - 1,
-                z,
-            },
-            Coordinate {
-                x,
-                y: self.y + 1,
-                z,
-            },
-            Coordinate {
-                x,
-                y,
-                z: self.z - 1,
-            },
-            Coordinate {
-                x,
-                y,
-                z: self.z + 1,
-            },
-        ]
-    }
+pub fn parse_coordinate(i: &str) -> IResult<&str, Coordinate> {
+    map(
+        tuple((
+            complete::i32,
+            preceded(tag(","), complete::i32),
+            preceded(tag(","), complete::i32),
+        )),
+        |(x, y, z)| Coordinate { x, y, z },
+    )(i)
 }
-nomstr!(Coordinate);
 
-pub fn parse_input_a(i: &str) -> Vec<Coordinate> {
-    i.lines()
-        .map(|c| c.parse::<Coordinate>().unwrap())
-        .collect()
+pub fn parse_input_a(i: &str) -> Result<Vec<Coordinate>> {
+    parse_lines(i, |l| parse_all(l, parse_coordinate))
 }
 
 pub fn process_input_a(coordinates: &[Coordinate]) -> usize {
-    let cubes: HashSet<Coordinate> = HashSet::from_iter(coordinates.iter().map(|&c| c));
+    let cubes: HashSet<Coordinate> = coordinates.iter().copied().collect();
     cubes
         .iter()
         .map(|&cube| {
             // Count the sides without neighbor.
-            cube.neighbors()
-                .iter()
-                .filter(|c| cubes.get(&c).is_none())
-                .count()
+            cube.neighbours6().filter(|c| !cubes.contains(c)).count()
         })
         .sum::<usize>()
 }
@@ -109,7 +48,7 @@ pub fn process_input_a(coordinates: &[Coordinate]) -> usize {
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "2,2,2
+    const RAW_INPUT: &str = "2,2,2
 1,2,2
 3,2,2
 2,1,2
@@ -126,11 +65,20 @@ mod test {
 
     #[test]
     fn test_day18a_sample() {
-        let cubes = parse_input_a(RAW_INPUT);
+        let cubes = parse_input_a(RAW_INPUT).unwrap();
         let not_connected = process_input_a(&cubes);
         assert_eq!(not_connected, 64);
     }
 
     #[test]
     fn test_day18b_sample() {}
+
+    #[test]
+    fn test_parse_coordinate() {
+        assert_eq!(
+            parse_all("1,-2,3", parse_coordinate),
+            Ok(Coordinate { x: 1, y: -2, z: 3 })
+        );
+        assert!(parse_input_a("1,2\n").is_err());
+    }
 }
@@ -1,44 +1,57 @@
-use std::fs;
-
-use itertools::Itertools;
 use nom::{
     branch::alt,
-    character::complete::{self, line_ending},
-    combinator::{eof, iterator},
+    character::complete::{self, line_ending, multispace0},
+    combinator::{all_consuming, eof, iterator},
     multi::separated_list1,
     sequence::terminated,
-    IResult,
+    Finish, IResult,
 };
 
-pub fn day20a() -> String {
-    let data = fs::read_to_string("assets/day20.txt").expect("Could not load file");
-    let (_, encrypted_file) = parse_input(&data).unwrap();
-    process_input_a(&encrypted_file, 1).to_string()
-}
+use crate::{
+    error::{AocError, Result},
+    solution,
+};
 
-pub fn day20b() -> String {
-    let data = fs::read_to_string("assets/day20.txt").expect("Could not load file");
-    let (_, mut encrypted_file) = parse_input(&data).unwrap();
-    prep_input_b(&mut encrypted_file);
-    process_input_a(&encrypted_file, 10).to_string()
-}
+solution!(Day20a, 20, A, parse_input_a, |encrypted_file| {
+    process_input_a(encrypted_file, 1)
+});
+solution!(Day20b, 20, B, parse_input_a, |encrypted_file| {
+    prep_input_b(encrypted_file);
+    process_input_a(encrypted_file, 10)
+});
+solution!(Day20aChris, 20, A, "chris", Ok, |input| process_part1(
+    input
+));
 
 pub fn parse_input(i: &str) -> IResult<&str, Vec<i64>> {
     separated_list1(complete::line_ending, complete::i64)(i)
 }
 
-pub fn process_input_a(encrypted_file: &[i64], mix: u8) -> i64 {
-    let id_map = encrypted_file
+pub fn parse_input_a(i: &str) -> Result<Vec<i64>> {
+    all_consuming(terminated(parse_input, multispace0))(i)
+        .finish()
+        .map(|(_, encrypted_file)| encrypted_file)
+        .map_err(|e| AocError::from_nom(i, e))
+}
+
+pub fn process_input_a(encrypted_file: &[i64], mix: u8) -> Result<i64> {
+    if encrypted_file.len() < 2 {
+        return Err(AocError::invalid_state("There is nothing to mix"));
+    }
+
+    let mut state = encrypted_file
         .iter()
         .enumerate()
         .map(|(i, &v)| (i, v))
         .collect::<Vec<(usize, i64)>>();
-    let mut state = id_map.clone();
 
     // Mix the map.
     for _ in 0..mix {
         for index in 0..encrypted_file.len() {
-            let position = state.iter().position(|(i, _v)| index == *i).unwrap();
+            let position = state
+                .iter()
+                .position(|(i, _v)| index == *i)
+                .ok_or_else(|| AocError::invalid_state(format!("Number {index} was lost")))?;
             let (i, v) = state.remove(position);
             // The error here was using encrypted_file.len() instead of state.len().
             // since state is shorter by one at that time, it makes sense to use
@@ -49,16 +62,19 @@ pub fn process_input_a(encrypted_file: &[i64], mix: u8) -> i64 {
     }
 
     // Decrypt.
-    let zero_pos = state.iter().position(|(_i, v)| *v == 0).unwrap();
-    (1..=3)
+    let zero_pos = state
+        .iter()
+        .position(|(_i, v)| *v == 0)
+        .ok_or_else(|| AocError::invalid_state("There is no 0 in the file"))?;
+    Ok((1..=3)
         .map(|i| state[(i * 1000 + zero_pos) % state.len()].1)
-        .sum()
+        .sum())
 }
 
 pub fn prep_input_b(encrypted_file: &mut [i64]) {
     const DECRYPTION_KEY: i64 = 811589153;
     for v in encrypted_file {
-        *v = *v * DECRYPTION_KEY;
+        *v *= DECRYPTION_KEY;
     }
 }
 
@@ -71,14 +87,19 @@ fn numbers(input: &str) -> IResult<&str, Vec<(usize, i64)>> {
     Ok((input, numbers))
 }
 
-pub fn process_part1(input: &str) -> i64 {
-    let (_, numbers) = numbers(input).unwrap();
+pub fn process_part1(input: &str) -> Result<i64> {
+    let (_, numbers) = all_consuming(terminated(numbers, multispace0))(input)
+        .finish()
+        .map_err(|e| AocError::from_nom(input, e))?;
+    if numbers.len() < 2 {
+        return Err(AocError::invalid_state("There is nothing to mix"));
+    }
     let mut state = numbers.clone();
     for (id, _value) in numbers.iter() {
         let index = state
             .iter()
             .position(|state_value| state_value.0 == *id)
-            .unwrap();
+            .ok_or_else(|| AocError::invalid_state(format!("Number {id} was lost")))?;
 
         let current = state.remove(index);
         let added = index as i64 + current.1;
@@ -87,18 +108,21 @@ pub fn process_part1(input: &str) -> i64 {
         state.insert(new_index as usize, current);
     }
 
-    let zero_pos = state.iter().position(|v| v.1 == 0).unwrap();
+    let zero_pos = state
+        .iter()
+        .position(|v| v.1 == 0)
+        .ok_or_else(|| AocError::invalid_state("There is no 0 in the file"))?;
     let a = state[(1000 + zero_pos) % state.len()].1;
     let b = state[(2000 + zero_pos) % state.len()].1;
     let c = state[(3000 + zero_pos) % state.len()].1;
-    a + b + c
+    Ok(a + b + c)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "1
+    const RAW_INPUT: &str = "1
 2
 -3
 3
@@ -110,18 +134,31 @@ mod test {
     #[test]
     fn test_day20a_sample() {
         let (_, encrypted_file) = parse_input(RAW_INPUT).unwrap();
-        assert_eq!(process_input_a(&encrypted_file, 1), 3);
+        assert_eq!(process_input_a(&encrypted_file, 1).unwrap(), 3);
     }
 
     #[test]
     fn test_day20a_chris() {
-        assert_eq!(process_part1(RAW_INPUT), 3);
+        assert_eq!(process_part1(RAW_INPUT).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_day20a_chris_invalid_input() {
+        assert_eq!(
+            process_part1("1\nx\n").unwrap_err().to_string(),
+            "Parse error on line 2: Eof failed on `x`"
+        );
+        assert_eq!(
+            process_part1("1\n2\n").unwrap_err().to_string(),
+            "Invalid puzzle state: There is no 0 in the file"
+        );
+        assert!(process_part1("0\n").is_err());
     }
 
     #[test]
     fn test_day20b_sample() {
         let (_, mut encrypted_file) = parse_input(RAW_INPUT).unwrap();
         prep_input_b(&mut encrypted_file);
-        assert_eq!(process_input_a(&encrypted_file, 10), 1623178306);
+        assert_eq!(process_input_a(&encrypted_file, 10).unwrap(), 1623178306);
     }
 }
@@ -1,34 +1,27 @@
-use crate::nomstr;
+use crate::{
+    error::{AocError, Result},
+    nomstr, solution,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alpha1, line_ending, space1},
-    combinator::value,
-    error::Error,
+    character::complete::{self, alpha1, line_ending, multispace0, space1},
+    combinator::{all_consuming, value},
     multi::separated_list1,
-    sequence::{delimited, preceded},
+    sequence::{delimited, preceded, terminated},
     Finish, IResult, Parser,
 };
 use petgraph::{
-    dot::{Config, Dot},
-    graph::{self, NodeIndex},
+    graph::NodeIndex,
     visit::{Topo, Walker},
     Graph,
 };
-use std::{collections::HashMap, fs, str::FromStr};
-
-pub fn day21a() -> String {
-    let data = fs::read_to_string("assets/day21.txt").expect("Could not load file");
-    let nodes = parse_input_a(&data);
-    let (graph, node_map) = build_graph(&nodes);
-    let root = process_input_a(&graph, &node_map);
-    root.to_string()
-}
+use std::collections::HashMap;
 
-pub fn day21b() -> String {
-    let data = fs::read_to_string("assets/day21.txt").expect("Could not load file");
-    data
-}
+solution!(Day21a, 21, A, parse_input_a, |nodes| {
+    let (graph, node_map) = build_graph(nodes)?;
+    process_input_a(&graph, &node_map)
+});
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Operator {
@@ -61,18 +54,15 @@ pub enum Operation<'a> {
 }
 
 impl<'a> Operation<'a> {
-    fn compute(i: &'a str) -> IResult<&str, Self> {
+    fn compute(i: &'a str) -> IResult<&'a str, Self> {
         let (i, lhs) = alpha1(i)?;
         let (i, operator) = delimited(space1, Operator::parse, space1)(i)?;
         let (i, rhs) = alpha1(i)?;
         Ok((i, Operation::Compute { lhs, operator, rhs }))
     }
 
-    pub fn parse(i: &'a str) -> IResult<&str, Self> {
-        alt((
-            complete::i64.map(|n| Operation::Number(n)),
-            Operation::compute,
-        ))(i)
+    pub fn parse(i: &'a str) -> IResult<&'a str, Self> {
+        alt((complete::i64.map(Operation::Number), Operation::compute))(i)
     }
 }
 // nomstr!(Operation);
@@ -84,7 +74,7 @@ pub struct Node<'a> {
 }
 
 impl<'a> Node<'a> {
-    pub fn parse(i: &'a str) -> IResult<&str, Self> {
+    pub fn parse(i: &'a str) -> IResult<&'a str, Self> {
         let (i, id) = alpha1(i)?;
         let (i, operation) = preceded(tag(": "), Operation::parse)(i)?;
         Ok((i, Node { id, operation }))
@@ -92,12 +82,19 @@ impl<'a> Node<'a> {
 }
 // nomstr!(Node);
 
-pub fn parse_input_a(i: &str) -> Vec<Node> {
-    let (_, nodes) = separated_list1(line_ending, Node::parse)(i).unwrap();
-    nodes
+pub fn parse_input_a(i: &str) -> Result<Vec<Node<'_>>> {
+    all_consuming(terminated(
+        separated_list1(line_ending, Node::parse),
+        multispace0,
+    ))(i)
+    .finish()
+    .map(|(_, nodes)| nodes)
+    .map_err(|e| AocError::from_nom(i, e))
 }
 
-pub fn build_graph<'a>(nodes: &'a [Node]) -> (Graph<&'a str, ()>, HashMap<NodeIndex, Node<'a>>) {
+type NodeMap<'a> = HashMap<NodeIndex, Node<'a>>;
+
+pub fn build_graph<'a>(nodes: &'a [Node]) -> Result<(Graph<&'a str, ()>, NodeMap<'a>)> {
     let mut graph = Graph::<&'a str, ()>::new();
 
     // Add nodes.
@@ -127,9 +124,14 @@ pub fn build_graph<'a>(nodes: &'a [Node]) -> (Graph<&'a str, ()>, HashMap<NodeIn
             rhs,
         } = node.operation
         {
-            let left = node_id_map.get(&lhs).unwrap();
-            let right = node_id_map.get(&rhs).unwrap();
-            let to = node_id_map.get(&node.id).unwrap();
+            let index = |id: &str| {
+                node_id_map
+                    .get(id)
+                    .ok_or_else(|| AocError::invalid_state(format!("Unknown monkey {id}")))
+            };
+            let left = index(lhs)?;
+            let right = index(rhs)?;
+            let to = index(node.id)?;
             graph.add_edge(*left, *to, ());
             graph.add_edge(*right, *to, ());
         }
@@ -137,30 +139,40 @@ pub fn build_graph<'a>(nodes: &'a [Node]) -> (Graph<&'a str, ()>, HashMap<NodeIn
 
     // println!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
 
-    (graph, node_map)
+    Ok((graph, node_map))
 }
 
 pub fn process_input_a<'a>(
     graph: &Graph<&'a str, ()>,
     node_map: &HashMap<NodeIndex, Node<'a>>,
-) -> i64 {
+) -> Result<i64> {
     let mut node_values: HashMap<&str, i64> = HashMap::new();
-    let topological = Topo::new(&graph);
+    let topological = Topo::new(graph);
     for node_id in topological.iter(graph) {
-        let current_node = node_map.get(&node_id).unwrap();
+        let current_node = node_map
+            .get(&node_id)
+            .ok_or_else(|| AocError::invalid_state(format!("Unknown monkey {}", graph[node_id])))?;
         match current_node.operation {
             Operation::Number(n) => {
                 node_values.insert(current_node.id, n);
             }
             Operation::Compute { lhs, operator, rhs } => {
-                let left = node_values.get(&lhs).unwrap();
-                let right = node_values.get(&rhs).unwrap();
+                let value = |id: &str| {
+                    node_values.get(id).copied().ok_or_else(|| {
+                        AocError::invalid_state(format!("Monkey {id} has no value yet"))
+                    })
+                };
+                let left = value(lhs)?;
+                let right = value(rhs)?;
                 match operator {
                     Operator::Add => {
                         node_values.insert(current_node.id, left + right);
                     }
                     Operator::Divide => {
-                        node_values.insert(current_node.id, left / right);
+                        let value = left.checked_div(right).ok_or_else(|| {
+                            AocError::invalid_state(format!("{} divides by 0", current_node.id))
+                        })?;
+                        node_values.insert(current_node.id, value);
                     }
                     Operator::Multiply => {
                         node_values.insert(current_node.id, left * right);
@@ -172,14 +184,17 @@ pub fn process_input_a<'a>(
             }
         }
     }
-    *node_values.get("root").unwrap()
+    node_values
+        .get("root")
+        .copied()
+        .ok_or_else(|| AocError::invalid_state("The root monkey cannot be computed"))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "dbpl: 5
+    const RAW_INPUT: &str = "dbpl: 5
 root: pppw + sjmn
 cczh: sllz + lgvd
 zczc: 2
@@ -198,12 +213,31 @@ hmdt: 32
 
     #[test]
     fn test_day21a_sample() {
-        let nodes = parse_input_a(RAW_INPUT);
-        let (graph, node_map) = build_graph(&nodes);
-        let root = process_input_a(&graph, &node_map);
+        let nodes = parse_input_a(RAW_INPUT).unwrap();
+        let (graph, node_map) = build_graph(&nodes).unwrap();
+        let root = process_input_a(&graph, &node_map).unwrap();
         assert_eq!(root, 152);
     }
 
+    #[test]
+    fn test_day21a_unknown_monkey() {
+        let nodes = parse_input_a("root: pppw + sjmn\npppw: 2\n").unwrap();
+        let error = build_graph(&nodes).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid puzzle state: Unknown monkey sjmn"
+        );
+
+        let nodes = parse_input_a(RAW_INPUT).unwrap();
+        let (graph, mut node_map) = build_graph(&nodes).unwrap();
+        node_map.retain(|_, n| n.id != "root");
+        let error = process_input_a(&graph, &node_map).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid puzzle state: Unknown monkey root"
+        );
+    }
+
     #[test]
     fn test_day21b_sample() {}
 }
@@ -1,68 +1,26 @@
+use log::debug;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, line_ending},
-    combinator::value,
-    multi::{many1, separated_list1},
-    IResult, Parser,
+    character::complete::{self, multispace0},
+    combinator::{all_consuming, value},
+    multi::many1,
+    sequence::terminated,
+    Finish, IResult, Parser,
+};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use crate::{
+    error::{AocError, Result},
+    geom::{Direction, Turn},
+    grid::{Grid, Position},
+    solution,
+    visualize::{Frame, NullVisualizer, Visualizer},
 };
-use std::{fmt::Display, fs};
-
-pub fn day22a() -> String {
-    let data = fs::read_to_string("assets/day22.txt").expect("Could not load file");
-    let (i, jungle) = Jungle::parse(&data).unwrap();
-    let (_, motions) = parse_motions(i.trim()).unwrap();
-    let password = process_input_a(jungle, &motions);
-    password.to_string()
-    // 33242 is too low
-}
-
-pub fn day22b() -> String {
-    let data = fs::read_to_string("assets/day22.txt").expect("Could not load file");
-    data
-}
-
-#[derive(Debug)]
-pub enum Direction {
-    Down,
-    Left,
-    Right,
-    Up,
-}
-
-impl Direction {
-    pub fn reverse(&self) -> Self {
-        match self {
-            Direction::Down => Direction::Up,
-            Direction::Left => Direction::Right,
-            Direction::Right => Direction::Left,
-            Direction::Up => Direction::Down,
-        }
-    }
-
-    pub fn turn(&self, turn: Turn) -> Self {
-        match turn {
-            Turn::Clockwise => match self {
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
-                Direction::Up => Direction::Right,
-                Direction::Right => Direction::Down,
-            },
-            Turn::Counterclockwise => match self {
-                Direction::Down => Direction::Right,
-                Direction::Right => Direction::Up,
-                Direction::Up => Direction::Left,
-                Direction::Left => Direction::Down,
-            },
-        }
-    }
-}
 
-#[derive(Debug, Clone, Copy)]
-pub enum Turn {
-    Clockwise,
-    Counterclockwise,
-}
+solution!(Day22a, 22, A, parse_input_a, |(jungle, motions)| {
+    process_input_a(jungle, motions, &mut NullVisualizer)
+});
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tile {
@@ -70,94 +28,105 @@ pub enum Tile {
     Corridor,
 }
 
-impl Tile {
-    pub fn parse(i: &str) -> IResult<&str, Self> {
-        alt((value(Tile::Wall, tag("#")), value(Tile::Corridor, tag("."))))(i)
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 pub enum Motion {
     Pace(u32),
     Turn(Turn),
 }
 
+impl Display for Motion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Motion::Pace(steps) => write!(f, "{steps}"),
+            Motion::Turn(Turn::Clockwise) => write!(f, "R"),
+            Motion::Turn(Turn::Counterclockwise) => write!(f, "L"),
+        }
+    }
+}
+
 impl Motion {
     pub fn parse(i: &str) -> IResult<&str, Self> {
         alt((
-            complete::u32.map(|m| Motion::Pace(m)),
+            complete::u32.map(Motion::Pace),
             value(Motion::Turn(Turn::Clockwise), tag("R")),
             value(Motion::Turn(Turn::Counterclockwise), tag("L")),
         ))(i)
     }
 }
 
-#[derive(Debug)]
-pub struct Jungle(Vec<Vec<Option<Tile>>>);
+#[derive(Debug, Clone)]
+pub struct Jungle(Grid<Option<Tile>>);
 
 impl Jungle {
-    fn parse_cell(i: &str) -> IResult<&str, Option<Tile>> {
-        alt((value(None, tag(" ")), Tile::parse.map(|t| Some(t))))(i)
-    }
-
-    pub fn parse_row(i: &str) -> IResult<&str, Vec<Option<Tile>>> {
-        many1(Jungle::parse_cell)(i)
+    fn parse_cell(c: char) -> Result<Option<Tile>, String> {
+        match c {
+            ' ' => Ok(None),
+            '#' => Ok(Some(Tile::Wall)),
+            '.' => Ok(Some(Tile::Corridor)),
+            _ => Err(format!("Invalid tile '{c}'")),
+        }
     }
 
-    pub fn parse(i: &str) -> IResult<&str, Self> {
-        let (i, jungle) = separated_list1(line_ending, Jungle::parse_row)(i)?;
-        Ok((i, Jungle(jungle)))
-    }
-
-    pub fn find_start(&self) -> Position {
-        for (y, row) in self.0.iter().enumerate() {
-            for (x, cell) in row.iter().enumerate() {
-                if let Some(t) = cell {
-                    if *t == Tile::Corridor {
-                        return Position { x, y };
-                    }
-                }
-            }
-        }
-        panic!("We messed up, there is no starting point!");
+    pub fn find_start(&self) -> Result<Position> {
+        self.0
+            .position(|cell| cell == &Some(Tile::Corridor))
+            .ok_or_else(|| AocError::invalid_state("We messed up, there is no starting point!"))
     }
 
     pub fn height(&self) -> usize {
-        self.0.len()
+        self.0.height()
     }
 
     pub fn width(&self) -> usize {
-        self.0[0].len()
+        self.0.width()
     }
 
     pub fn tile(&self, position: Position) -> Option<Tile> {
-        if let Some(row) = self.0.get(position.y) {
-            if let Some(tile) = row.get(position.x) {
-                return *tile;
-                // return self.0[position.y][position.x]
-            }
+        self.0.get(position).copied().flatten()
+    }
+
+    /// Draw the map like the puzzle does, with the last facing of the traveler on each
+    /// tile of its trail.
+    pub fn render_trail(&self, trail: &HashMap<Position, Direction>) -> String {
+        let mut picture = String::with_capacity((self.width() + 1) * self.height());
+        for y in 0..self.height() {
+            let row = (0..self.width())
+                .map(|x| {
+                    let position = Position { x, y };
+                    match (trail.get(&position), self.tile(position)) {
+                        (Some(Direction::Up), _) => '^',
+                        (Some(Direction::Right), _) => '>',
+                        (Some(Direction::Down), _) => 'v',
+                        (Some(Direction::Left), _) => '<',
+                        (None, Some(Tile::Wall)) => '#',
+                        (None, Some(Tile::Corridor)) => '.',
+                        (None, None) => ' ',
+                    }
+                })
+                .collect::<String>();
+            picture.push_str(row.trim_end());
+            picture.push('\n');
         }
-        None
+        picture
+    }
+}
+
+impl FromStr for Jungle {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Jungle(Grid::parse_ragged(s, Jungle::parse_cell)?))
     }
 }
 
 impl Display for Jungle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut s = String::new();
-        for row in &self.0 {
-            for cell in row {
-                match cell {
-                    Some(tile) => match tile {
-                        Tile::Wall => s.push('#'),
-                        Tile::Corridor => s.push('.'),
-                    },
-                    None => s.push('_'),
-                }
-            }
-            s.push('\n');
-        }
-
-        write!(f, "{}", s)
+        let jungle = self.0.render(|cell| match cell {
+            Some(Tile::Wall) => '#',
+            Some(Tile::Corridor) => '.',
+            None => '_',
+        });
+        write!(f, "{jungle}")
     }
 }
 
@@ -165,79 +134,117 @@ pub fn parse_motions(i: &str) -> IResult<&str, Vec<Motion>> {
     many1(Motion::parse)(i)
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct Position {
-    x: usize,
-    y: usize,
+pub fn parse_input_a(i: &str) -> Result<(Jungle, Vec<Motion>)> {
+    let (jungle, motions) = i
+        .split_once("\n\n")
+        .ok_or_else(|| AocError::parse(i.lines().count().max(1), "The path is missing"))?;
+    let offset = jungle.lines().count() + 1;
+    let motions = all_consuming(terminated(parse_motions, multispace0))(motions)
+        .finish()
+        .map(|(_, motions)| motions)
+        .map_err(|e| AocError::from_nom(motions, e).shift_lines(offset))?;
+    Ok((jungle.parse::<Jungle>()?, motions))
 }
 
 #[derive(Debug)]
-pub struct Traveler {
-    jungle: Jungle,
+pub struct Traveler<'a> {
+    jungle: &'a Jungle,
     facing: Direction,
     position: Position,
 }
 
-impl Traveler {
-    pub fn wrap_around(&self) -> Position {
+impl Traveler<'_> {
+    pub fn wrap_around(&self) -> Result<Position> {
         match self.facing {
             Direction::Down => {
                 for i in 0..=self.jungle.height() {
-                    if let Some(cell) = self.jungle.0[i].get(self.position.x) {
-                        if let Some(_tile) = cell {
-                            return Position {
-                                x: self.position.x,
-                                y: i,
-                            };
-                        }
+                    if self
+                        .jungle
+                        .tile(Position {
+                            x: self.position.x,
+                            y: i,
+                        })
+                        .is_some()
+                    {
+                        return Ok(Position {
+                            x: self.position.x,
+                            y: i,
+                        });
                     }
                 }
-                panic!("There is no valid tiles on column {}!", self.position.x);
+                Err(AocError::invalid_state(format!(
+                    "There is no valid tiles on column {}!",
+                    self.position.x
+                )))
             }
 
             Direction::Left => {
                 for i in (0..self.jungle.width()).rev() {
-                    if let Some(cell) = self.jungle.0[self.position.y].get(i) {
-                        if let Some(_tile) = cell {
-                            return Position {
-                                x: i,
-                                y: self.position.y,
-                            };
-                        }
+                    if self
+                        .jungle
+                        .tile(Position {
+                            x: i,
+                            y: self.position.y,
+                        })
+                        .is_some()
+                    {
+                        return Ok(Position {
+                            x: i,
+                            y: self.position.y,
+                        });
                     }
                 }
-                panic!("There is no valid tiles on row {}!", self.position.y);
+                Err(AocError::invalid_state(format!(
+                    "There is no valid tiles on row {}!",
+                    self.position.y
+                )))
             }
             Direction::Right => {
                 for i in 0..self.jungle.width() {
-                    if let Some(cell) = self.jungle.0[self.position.y].get(i) {
-                        if let Some(_tile) = cell {
-                            return Position {
-                                x: i,
-                                y: self.position.y,
-                            };
-                        }
+                    if self
+                        .jungle
+                        .tile(Position {
+                            x: i,
+                            y: self.position.y,
+                        })
+                        .is_some()
+                    {
+                        return Ok(Position {
+                            x: i,
+                            y: self.position.y,
+                        });
                     }
                 }
-                panic!("There is no valid tiles on row {}!", self.position.y);
+                Err(AocError::invalid_state(format!(
+                    "There is no valid tiles on row {}!",
+                    self.position.y
+                )))
             }
             Direction::Up => {
                 for i in (0..self.jungle.height()).rev() {
-                    if let Some(cell) = self.jungle.0[i].get(self.position.x) {
-                        if let Some(_tile) = cell {
-                            return Position {
-                                x: self.position.x,
-                                y: i,
-                            };
-                        }
+                    if self
+                        .jungle
+                        .tile(Position {
+                            x: self.position.x,
+                            y: i,
+                        })
+                        .is_some()
+                    {
+                        return Ok(Position {
+                            x: self.position.x,
+                            y: i,
+                        });
                     }
                 }
-                panic!("There is no valid tiles on column {}!", self.position.x);
+                Err(AocError::invalid_state(format!(
+                    "There is no valid tiles on column {}!",
+                    self.position.x
+                )))
             }
         }
     }
 
-    pub fn next(&mut self) {
+    pub fn advance(&mut self) -> Result<()> {
         let next_position = match self.facing {
             Direction::Down => {
                 let y = self.position.y + 1;
@@ -248,7 +255,7 @@ impl Traveler {
 
                 // Are we going off the map or to a void?
                 if y >= self.jungle.height() || self.jungle.tile(new_position).is_none() {
-                    self.wrap_around()
+                    self.wrap_around()?
                 } else {
                     new_position
                 }
@@ -257,18 +264,18 @@ impl Traveler {
                 let x = self.position.x.checked_sub(1);
 
                 // Are we going off the map?
-                if x.is_none() {
-                    self.wrap_around()
-                } else {
+                if let Some(x) = x {
                     let new_position = Position {
-                        x: x.unwrap(),
+                        x,
                         y: self.position.y,
                     };
                     if self.jungle.tile(new_position).is_none() {
-                        self.wrap_around()
+                        self.wrap_around()?
                     } else {
                         new_position
                     }
+                } else {
+                    self.wrap_around()?
                 }
             }
             Direction::Right => {
@@ -280,7 +287,7 @@ impl Traveler {
 
                 // Are we going off the map?
                 if x >= self.jungle.width() || self.jungle.tile(new_position).is_none() {
-                    self.wrap_around()
+                    self.wrap_around()?
                 } else {
                     new_position
                 }
@@ -289,19 +296,19 @@ impl Traveler {
                 let y = self.position.y.checked_sub(1);
 
                 // Are we going off the map?
-                if y.is_none() {
-                    self.wrap_around()
-                } else {
+                if let Some(y) = y {
                     let new_position = Position {
                         x: self.position.x,
-                        y: y.unwrap(),
+                        y,
                     };
 
                     if self.jungle.tile(new_position).is_none() {
-                        self.wrap_around()
+                        self.wrap_around()?
                     } else {
                         new_position
                     }
+                } else {
+                    self.wrap_around()?
                 }
             }
         };
@@ -312,48 +319,64 @@ impl Traveler {
                 self.position = next_position;
             }
         }
+        Ok(())
     }
 }
 
-pub fn process_input_a(jungle: Jungle, motions: &[Motion]) -> usize {
-    let position = jungle.find_start();
+pub fn process_input_a(
+    jungle: &Jungle,
+    motions: &[Motion],
+    visualizer: &mut dyn Visualizer,
+) -> Result<usize> {
+    let (traveler, _trail) = walk(jungle, motions, visualizer)?;
+    let facing = match traveler.facing {
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Right => 0,
+        Direction::Up => 3,
+    };
+    Ok((1000 * (traveler.position.y + 1)) + (4 * (traveler.position.x + 1)) + facing)
+}
+
+/// Follow the path from the start, returning where the traveler ends up and its trail,
+/// the last facing of the traveler on each tile it went through.
+pub fn walk<'a>(
+    jungle: &'a Jungle,
+    motions: &[Motion],
+    visualizer: &mut dyn Visualizer,
+) -> Result<(Traveler<'a>, HashMap<Position, Direction>)> {
+    let position = jungle.find_start()?;
     let mut traveler = Traveler {
         jungle,
         position,
         facing: Direction::Right,
     };
-    dbg!(&traveler.position);
+    debug!("Start: {}", traveler.position);
+    let mut trail = HashMap::from([(traveler.position, traveler.facing)]);
     for motion in motions {
         match motion {
             Motion::Pace(steps) => {
                 for _step in 0..*steps {
-                    traveler.next();
+                    traveler.advance()?;
+                    trail.insert(traveler.position, traveler.facing);
                 }
-                // println!("[{},{}]", &traveler.position.x, &traveler.position.y);
             }
             Motion::Turn(turn) => {
-                // println!("Turn: {:?}", turn);
-                // print!("Turning from {:?} to ", traveler.facing);
                 traveler.facing = traveler.facing.turn(*turn);
-                // println!("{:?}", traveler.facing);
+                trail.insert(traveler.position, traveler.facing);
             }
         };
+        visualizer.draw(&|| Frame::new(motion.to_string(), jungle.render_trail(&trail)));
     }
-    let facing = match traveler.facing {
-        Direction::Down => 1,
-        Direction::Left => 2,
-        Direction::Right => 0,
-        Direction::Up => 3,
-    };
-    // dbg!(&traveler.position);
-    (1000 * (traveler.position.y + 1)) + (4 * (traveler.position.x + 1)) + facing
+    Ok((traveler, trail))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::visualize::Recorder;
 
-    const RAW_INPUT: &'static str = "        ...#
+    const RAW_INPUT: &str = "        ...#
         .#..
         #...
         ....
@@ -371,11 +394,40 @@ mod test {
 
     #[test]
     fn test_day22a_sample() {
-        let (i, jungle) = Jungle::parse(RAW_INPUT).unwrap();
-        // let (i, _) = alt((line_ending, space1))(i).unwrap();
-        let (_, motions) = parse_motions(i.trim()).unwrap();
-        let password = process_input_a(jungle, &motions);
+        let (jungle, motions) = parse_input_a(RAW_INPUT).unwrap();
+        let mut recorder = Recorder::default();
+        let password = process_input_a(&jungle, &motions, &mut recorder).unwrap();
         assert_eq!(password, 6032);
+        assert_eq!(recorder.frames.len(), motions.len());
+        assert_eq!(
+            recorder.frames.last().unwrap(),
+            &Frame::new(
+                "5",
+                "        >>v#
+        .#v.
+        #.v.
+        ..v.
+...#...v..v#
+>>>v...>#.>>
+..#v...#....
+...>>>>v..#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+"
+            )
+        );
+    }
+
+    #[test]
+    fn test_day22a_missing_path() {
+        for (input, line) in [("", 1), ("...#\n.#..\n", 2)] {
+            assert_eq!(
+                parse_input_a(input).unwrap_err().to_string(),
+                format!("Parse error on line {line}: The path is missing")
+            );
+        }
     }
 
     #[test]
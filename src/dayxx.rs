@@ -1,20 +1,25 @@
-use std::fs;
+use crate::{error::Result, solution};
 
-pub fn dayxxa() -> String {
-    let data = fs::read_to_string("assets/dayxx.txt").expect("Could not load file");
-    data
+solution!(Dayxxa, xx, A, parse_input_a, |data| process_input_a(data));
+solution!(Dayxxb, xx, B, parse_input_a, |data| process_input_b(data));
+
+pub fn parse_input_a(input: &str) -> Result<Vec<String>> {
+    Ok(input.lines().map(String::from).collect())
+}
+
+pub fn process_input_a(data: &[String]) -> Result<usize> {
+    Ok(data.len())
 }
 
-pub fn dayxxb() -> String {
-    let data = fs::read_to_string("assets/dayxx.txt").expect("Could not load file");
-    data
+pub fn process_input_b(data: &[String]) -> Result<usize> {
+    Ok(data.len())
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const RAW_INPUT: &'static str = "
+    const RAW_INPUT: &str = "
 ";
 
     #[test]
@@ -0,0 +1,361 @@
+use std::{fmt::Display, io, num::ParseIntError};
+
+use nom::{
+    character::complete::multispace0,
+    combinator::all_consuming,
+    error::{Error, ErrorKind},
+    sequence::terminated,
+    Finish, IResult,
+};
+
+use crate::solution::Part;
+
+/// Errors raised while loading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    /// A line of the input could not be parsed, lines being numbered from 1.
+    Parse {
+        line: usize,
+        message: String,
+    },
+    /// Every line of the input which could not be parsed.
+    BadLines(Vec<BadLine>),
+    /// A nom parser failed, or did not consume the whole input.
+    Syntax(SyntaxError),
+    /// The puzzle reached a state the solution cannot handle.
+    InvalidState(String),
+    /// A puzzle input could not be downloaded.
+    Fetch(String),
+    /// The configuration file is not valid.
+    Config(String),
+    /// There is no solution for this puzzle yet.
+    Unsolved {
+        day: u8,
+        part: Part,
+    },
+}
+
+pub type Result<T, E = AocError> = std::result::Result<T, E>;
+
+impl AocError {
+    pub fn parse<M: Display>(line: usize, message: M) -> Self {
+        AocError::Parse {
+            line,
+            message: message.to_string(),
+        }
+    }
+
+    /// A parse error for the line of `input` where `rest`, its unparsed remainder, starts.
+    ///
+    /// ```
+    /// use aoc2022::error::AocError;
+    ///
+    /// let input = "1\n2\nthree\n";
+    /// let error = AocError::parse_at(input, &input[4..], "not a number");
+    /// assert_eq!(error.to_string(), "Parse error on line 3: not a number");
+    /// ```
+    pub fn parse_at<M: Display>(input: &str, rest: &str, message: M) -> Self {
+        let offset = input.len().saturating_sub(rest.len());
+        let line = input[..offset].matches('\n').count() + 1;
+        AocError::parse(line, message)
+    }
+
+    /// A parse error for a nom parser which failed on `input`.
+    pub fn from_nom(input: &str, error: Error<&str>) -> Self {
+        let found = error.input.lines().next().unwrap_or_default();
+        AocError::parse_at(
+            input,
+            error.input,
+            format!("{:?} failed on `{found}`", error.code),
+        )
+    }
+
+    /// Shift the line of a parse error, for a section of the input preceded by `offset`
+    /// other lines.
+    pub fn shift_lines(self, offset: usize) -> Self {
+        match self {
+            AocError::Parse { line, message } => AocError::Parse {
+                line: line + offset,
+                message,
+            },
+            AocError::Syntax(e) => AocError::Syntax(e.shift_lines(offset)),
+            _ => self,
+        }
+    }
+
+    pub fn invalid_state<M: Display>(message: M) -> Self {
+        AocError::InvalidState(message.to_string())
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io(e) => write!(f, "{e}"),
+            AocError::Parse { line, message } => {
+                write!(f, "Parse error on line {line}: {message}")
+            }
+            AocError::BadLines(lines) => {
+                write!(f, "Parse errors on {} line(s):", lines.len())?;
+                for line in lines {
+                    write!(f, "\n  {line}")?;
+                }
+                Ok(())
+            }
+            AocError::Syntax(e) => write!(f, "Syntax error: {e:#}"),
+            AocError::InvalidState(message) => write!(f, "Invalid puzzle state: {message}"),
+            AocError::Fetch(message) => write!(f, "Cannot fetch the input: {message}"),
+            AocError::Config(message) => write!(f, "Invalid configuration: {message}"),
+            AocError::Unsolved { day, part } => {
+                write!(f, "Day {day} part {part} is not solved yet")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(item: io::Error) -> Self {
+        AocError::Io(item)
+    }
+}
+
+impl From<Vec<BadLine>> for AocError {
+    fn from(item: Vec<BadLine>) -> Self {
+        AocError::BadLines(item)
+    }
+}
+
+impl From<SyntaxError> for AocError {
+    fn from(item: SyntaxError) -> Self {
+        AocError::Syntax(item)
+    }
+}
+
+/// A line of the input which could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BadLine {
+    /// Line number, starting from 1.
+    pub line: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl Display for BadLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}: `{}`", self.line, self.message, self.text)
+    }
+}
+
+/// The position where a nom parser failed, or where the input it left unparsed starts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// Line and column, both starting from 1.
+    pub line: usize,
+    pub column: usize,
+    pub code: ErrorKind,
+    /// The text of the line at fault.
+    pub snippet: String,
+}
+
+impl SyntaxError {
+    /// Locate the error of a parser run against `input`.
+    pub fn new(input: &str, error: Error<&str>) -> Self {
+        let mut offset = input.len().saturating_sub(error.input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line_start = input[..offset].rfind('\n').map_or(0, |p| p + 1);
+        SyntaxError {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            code: error.code,
+            snippet: input[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    /// Shift the line of the error, for a section of the input preceded by `offset` other
+    /// lines.
+    pub fn shift_lines(self, offset: usize) -> Self {
+        SyntaxError {
+            line: self.line + offset,
+            ..self
+        }
+    }
+}
+
+/// The alternate form, `{:#}`, shows the line at fault with a caret under the column.
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.code {
+            ErrorKind::Eof => write!(f, "Unexpected trailing input")?,
+            code => write!(f, "{code:?} failed")?,
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)?;
+        if f.alternate() {
+            write!(
+                f,
+                "\n  {}\n  {:>width$}",
+                self.snippet,
+                "^",
+                width = self.column
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+/// Run a nom parser against the whole input, only trailing whitespace being allowed
+/// after what it parsed.
+///
+/// ```
+/// use aoc2022::error::parse_all;
+/// use nom::character::complete::u32;
+///
+/// assert_eq!(parse_all("12\n", u32), Ok(12));
+/// assert_eq!(
+///     format!("{:#}", parse_all("12x", u32).unwrap_err()),
+///     "Unexpected trailing input at line 1, column 3\n  12x\n    ^"
+/// );
+/// ```
+pub fn parse_all<'a, T, F>(input: &'a str, parser: F) -> Result<T, SyntaxError>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, item)| item)
+        .map_err(|e| SyntaxError::new(input, e))
+}
+
+/// An error raised while parsing a single line of the input.
+pub trait LineError: Display + Sized {
+    /// The error of the whole input, the line being the `line`th one, starting from 1.
+    fn at_line(self, line: usize, text: &str) -> AocError {
+        AocError::parse(line, format!("{self}: `{text}`"))
+    }
+}
+
+impl LineError for String {}
+impl LineError for &str {}
+impl LineError for ParseIntError {}
+impl LineError for Error<&str> {}
+
+/// The error already knows its line and column within the line, only the line is shifted.
+impl LineError for SyntaxError {
+    fn at_line(self, line: usize, _text: &str) -> AocError {
+        AocError::Syntax(self.shift_lines(line - 1))
+    }
+}
+
+/// Parse each line of the input, stopping at the first failure.
+///
+/// ```
+/// use aoc2022::error::parse_lines;
+///
+/// assert_eq!(parse_lines("1\n2\n", |l| l.parse::<u8>()).unwrap(), vec![1, 2]);
+/// assert_eq!(
+///     parse_lines("1\nx\n", |l| l.parse::<u8>()).unwrap_err().to_string(),
+///     "Parse error on line 2: invalid digit found in string: `x`"
+/// );
+/// ```
+pub fn parse_lines<'a, T, E, F>(input: &'a str, parse: F) -> Result<Vec<T>>
+where
+    E: LineError,
+    F: Fn(&'a str) -> Result<T, E>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(n, l)| parse(l).map_err(|e| e.at_line(n + 1, l)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nom::{
+        bytes::complete::tag, character::complete::u32, multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    #[test]
+    fn test_from_nom() {
+        let input = "1\n2\nx\n";
+        let error = Error::new(&input[4..], ErrorKind::Digit);
+        assert_eq!(
+            AocError::from_nom(input, error).to_string(),
+            "Parse error on line 3: Digit failed on `x`"
+        );
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        let error = AocError::from(vec![
+            BadLine {
+                line: 2,
+                text: String::from("x"),
+                message: String::from("not a number"),
+            },
+            BadLine {
+                line: 5,
+                text: String::from(""),
+                message: String::from("empty"),
+            },
+        ]);
+        assert_eq!(
+            error.to_string(),
+            "Parse errors on 2 line(s):\n  line 2: not a number: `x`\n  line 5: empty: ``"
+        );
+    }
+
+    #[test]
+    fn test_shift_lines() {
+        let error = AocError::parse(2, "oops").shift_lines(5);
+        assert_eq!(error.to_string(), "Parse error on line 7: oops");
+        let error = AocError::invalid_state("oops").shift_lines(5);
+        assert_eq!(error.to_string(), "Invalid puzzle state: oops");
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let pair = |i| separated_pair(u32, tag(","), u32)(i);
+        assert_eq!(parse_all("1,2\n", pair), Ok((1, 2)));
+
+        let error = parse_all("1,2garbage", pair).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(
+            error.to_string(),
+            "Unexpected trailing input at line 1, column 4"
+        );
+
+        let error = AocError::from(
+            parse_all("1,2\n3,x", |i| separated_list1(tag("\n"), pair)(i)).unwrap_err(),
+        );
+        assert_eq!(
+            error.shift_lines(10).to_string(),
+            "Syntax error: Unexpected trailing input at line 12, column 1\n  3,x\n  ^"
+        );
+
+        let error = parse_lines("1,2\n3,x\n", |l| parse_all(l, pair)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Syntax error: Digit failed at line 2, column 3\n  3,x\n    ^"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    env, fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{config_dir, Config},
+    error::{AocError, Result},
+    input::{input_path, ASSETS_DIR},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+
+/// Environment variable holding the session token.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Environment variable overriding the base URL of the website.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+const USER_AGENT: &str = "github.com/rgreinho/aoc2022";
+
+/// Where a puzzle input was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already there and nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads the puzzle inputs into the assets directory.
+#[derive(Clone, Debug)]
+pub struct Fetcher {
+    base_url: String,
+    year: u16,
+    session: Option<String>,
+    session_file: Option<PathBuf>,
+    assets_dir: PathBuf,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str) -> Self {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            year: YEAR,
+            session: None,
+            session_file: session_file(),
+            assets_dir: PathBuf::from(ASSETS_DIR),
+        }
+    }
+
+    /// Configure a fetcher from the environment, reading the session token from
+    /// `$AOC_SESSION` or from the session file.
+    pub fn from_env() -> Self {
+        Fetcher::from_config(&Config::default())
+    }
+
+    /// Configure a fetcher from the environment and the configuration, which can move the
+    /// assets directory and the session file, or fetch the inputs of another year.
+    pub fn from_config(config: &Config) -> Self {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let session_file = config.session_file.clone().or_else(session_file);
+        Fetcher {
+            year: config.year.unwrap_or(YEAR),
+            session: session_token(session_file.as_deref()),
+            session_file,
+            assets_dir: config.assets_dir(),
+            ..Fetcher::new(&base_url)
+        }
+    }
+
+    pub fn with_session(mut self, session: &str) -> Self {
+        self.session = Some(session.trim().to_string());
+        self
+    }
+
+    pub fn with_year(mut self, year: u16) -> Self {
+        self.year = year;
+        self
+    }
+
+    pub fn with_assets_dir<P: AsRef<Path>>(mut self, assets_dir: P) -> Self {
+        self.assets_dir = assets_dir.as_ref().to_path_buf();
+        self
+    }
+
+    pub fn url(&self, day: u8) -> String {
+        format!("{}/{}/day/{day}/input", self.base_url, self.year)
+    }
+
+    /// Download the input of a day, unless it was already downloaded.
+    pub fn fetch(&self, day: u8) -> Result<Fetched> {
+        let path = input_path(&self.assets_dir, day);
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+
+        let session = self.session.as_ref().ok_or_else(|| {
+            AocError::Fetch(format!(
+                "No session token, set {SESSION_ENV} or write it to {}",
+                self.session_file
+                    .as_ref()
+                    .map_or(String::from("the session file"), |p| p
+                        .display()
+                        .to_string())
+            ))
+        })?;
+        let url = self.url(day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(code, _) => {
+                    AocError::Fetch(format!("{url} answered with HTTP {code}"))
+                }
+                e => AocError::Fetch(format!("Cannot reach {url}: {e}")),
+            })?;
+        let mut data = String::new();
+        response.into_reader().read_to_string(&mut data)?;
+
+        // Write to a temporary file first, so an interrupted download is not mistaken for
+        // an input later on.
+        fs::create_dir_all(&self.assets_dir)?;
+        let partial = path.with_extension("part");
+        fs::write(&partial, data)?;
+        fs::rename(&partial, &path)?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+/// Path of the file the session token can be stored in by default, in the
+/// [configuration directory](config_dir).
+pub fn session_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("session"))
+}
+
+/// The session token, from `$AOC_SESSION` or else from the session file.
+pub fn session_token(session_file: Option<&Path>) -> Option<String> {
+    env::var(SESSION_ENV)
+        .ok()
+        .or_else(|| fs::read_to_string(session_file?).ok())
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serve `count` requests with the given status and body, returning the request
+    /// lines and cookies received.
+    fn serve(count: usize, status: &str, body: &str) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for stream in listener.incoming().take(count) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if line.starts_with("GET") || line.starts_with("Cookie") {
+                        requests.push(line.trim().to_string());
+                    }
+                    line.clear();
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn temp_dir() -> PathBuf {
+        env::temp_dir().join(format!("aoc2022-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_url() {
+        let fetcher = Fetcher::new("https://example.com/");
+        assert_eq!(fetcher.url(6), "https://example.com/2022/day/6/input");
+        assert_eq!(
+            fetcher.with_year(2021).url(6),
+            "https://example.com/2021/day/6/input"
+        );
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (url, server) = serve(1, "200 OK", "1000\n2000\n");
+        let dir = temp_dir();
+        let fetcher = Fetcher::new(&url)
+            .with_session("secret\n")
+            .with_assets_dir(&dir);
+
+        let path = dir.join("day06.txt");
+        assert_eq!(fetcher.fetch(6).unwrap(), Fetched::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        assert_eq!(
+            server.join().unwrap(),
+            vec!["GET /2022/day/6/input HTTP/1.1", "Cookie: session=secret"]
+        );
+
+        // The server is gone: the input must come from the cache.
+        assert_eq!(fetcher.fetch(6).unwrap(), Fetched::Cached(path));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_error() {
+        let (url, server) = serve(1, "404 Not Found", "Not yet!");
+        let dir = temp_dir();
+        let fetcher = Fetcher::new(&url).with_assets_dir(&dir);
+        assert!(fetcher.fetch(25).is_err());
+
+        let fetcher = fetcher.with_session("secret");
+        assert_eq!(
+            fetcher.fetch(25).unwrap_err().to_string(),
+            format!("Cannot fetch the input: {url}/2022/day/25/input answered with HTTP 404")
+        );
+        server.join().unwrap();
+        assert!(!dir.join("day25.txt").exists());
+    }
+}
@@ -0,0 +1,331 @@
+use std::{collections::HashSet, fmt::Write, ops::RangeInclusive};
+
+use crate::geom::Point2;
+
+/// The days we can generate inputs for.
+pub const GENERATED_DAYS: &[u8] = &[11, 14, 15, 16, 20];
+
+/// A small seeded pseudo-random number generator (SplitMix64), so that an input can be
+/// generated again from its seed.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number within the range, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "The range {start}..={end} is empty");
+        let span = (end as i128 - start as i128 + 1) as u128;
+        (start as i128 + (self.next_u64() as u128 % span) as i128) as i64
+    }
+
+    /// An index below `len`, which must not be 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..=len as i64 - 1) as usize
+    }
+
+    /// Whether an event with a probability of `1 / n` happened.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.next_u64().is_multiple_of(n)
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Size of the generated input of a day, close to the real ones, if we can generate it.
+pub fn default_size(day: u8) -> Option<usize> {
+    match day {
+        11 => Some(8),
+        14 => Some(150),
+        15 => Some(30),
+        16 => Some(60),
+        20 => Some(5000),
+        _ => None,
+    }
+}
+
+/// The sizes the generator of a day accepts, if we can generate its inputs.
+pub fn valid_sizes(day: u8) -> Option<RangeInclusive<usize>> {
+    match day {
+        11 => Some(2..=256),
+        14 => Some(1..=usize::MAX),
+        15 => Some(1..=usize::MAX),
+        16 => Some(2..=26 * 26),
+        20 => Some(2..=usize::MAX),
+        _ => None,
+    }
+}
+
+/// Generate a random input for a day, if we know how to.
+///
+/// The meaning of `size` depends on the day, see the generator of each day. It must be
+/// within the [valid sizes](valid_sizes).
+pub fn generate(day: u8, size: usize, rng: &mut Rng) -> Option<String> {
+    match day {
+        11 => Some(day11(size, rng)),
+        14 => Some(day14(size, rng)),
+        15 => Some(day15(size, rng)),
+        16 => Some(day16(size, rng)),
+        20 => Some(day20(size, rng)),
+        _ => None,
+    }
+}
+
+/// Notes about `monkeys` monkeys, at least 2 and at most 256.
+///
+/// Like in the real inputs, a single monkey squares the worry levels, which can still
+/// overflow when an item keeps coming back to it.
+pub fn day11(monkeys: usize, rng: &mut Rng) -> String {
+    const PRIMES: &[i64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+    assert!((2..=256).contains(&monkeys), "Invalid monkey count");
+
+    let squaring = rng.index(monkeys);
+    let mut notes = Vec::new();
+    for id in 0..monkeys {
+        let items = (0..rng.range(1..=8))
+            .map(|_| rng.range(50..=99).to_string())
+            .collect::<Vec<_>>();
+        let operation = if id == squaring {
+            String::from("old * old")
+        } else if rng.one_in(2) {
+            format!("old + {}", rng.range(1..=8))
+        } else {
+            format!("old * {}", rng.range(2..=19))
+        };
+        // Throw to two other monkeys.
+        let mut target = || {
+            let t = rng.index(monkeys - 1);
+            if t >= id {
+                t + 1
+            } else {
+                t
+            }
+        };
+        let truthy = target();
+        let falsy = loop {
+            let falsy = target();
+            if falsy != truthy || monkeys == 2 {
+                break falsy;
+            }
+        };
+        notes.push(format!(
+            "Monkey {id}:
+  Starting items: {}
+  Operation: new = {operation}
+  Test: divisible by {}
+    If true: throw to monkey {truthy}
+    If false: throw to monkey {falsy}
+",
+            items.join(", "),
+            PRIMES[id % PRIMES.len()],
+        ));
+    }
+    notes.join("\n")
+}
+
+/// A scan of `paths` rock paths, at least 1, below the sand source at 500,0.
+pub fn day14(paths: usize, rng: &mut Rng) -> String {
+    let mut scan = String::new();
+    for _ in 0..paths {
+        let mut point = Point2::new(rng.range(450..=550), rng.range(10..=170));
+        let mut points = vec![point];
+        let mut horizontal = rng.one_in(2);
+        for _ in 0..rng.range(1..=5) {
+            let length = rng.range(-10..=10);
+            point = if horizontal {
+                Point2::new((point.x + length).clamp(440, 560), point.y)
+            } else {
+                Point2::new(point.x, (point.y + length).clamp(5, 180))
+            };
+            points.push(point);
+            horizontal = !horizontal;
+        }
+        let points = points
+            .iter()
+            .map(|p| format!("{},{}", p.x, p.y))
+            .collect::<Vec<_>>();
+        writeln!(scan, "{}", points.join(" -> ")).unwrap();
+    }
+    scan
+}
+
+/// The report of `sensors` sensors, at least 1, within 0..=4000000 like the real ones.
+///
+/// Each sensor has a single closest beacon, but the distress beacon of the second part
+/// is not guaranteed to be unique.
+pub fn day15(sensors: usize, rng: &mut Rng) -> String {
+    const AREA: RangeInclusive<i64> = 0..=4_000_000;
+    assert!(sensors > 0, "There must be a sensor");
+
+    let random_point = |rng: &mut Rng| Point2::new(rng.range(AREA), rng.range(AREA));
+    let beacons = (0..sensors / 2 + 1)
+        .map(|_| random_point(rng))
+        .collect::<HashSet<_>>();
+    let mut taken = beacons.clone();
+    let mut report = String::new();
+    let mut count = 0;
+    while count < sensors {
+        let sensor = random_point(rng);
+        let mut distances = beacons
+            .iter()
+            .map(|b| (sensor.manhattan(b), *b))
+            .collect::<Vec<_>>();
+        distances.sort();
+        // The closest beacon must be the only one at its distance.
+        let tied = distances.get(1).is_some_and(|d| d.0 == distances[0].0);
+        if tied || taken.contains(&sensor) {
+            continue;
+        }
+        taken.insert(sensor);
+        let beacon = distances[0].1;
+        writeln!(
+            report,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sensor.x, sensor.y, beacon.x, beacon.y
+        )
+        .unwrap();
+        count += 1;
+    }
+    report
+}
+
+/// A scan of `valves` connected valves, at least 2 and at most 676, including `AA`.
+pub fn day16(valves: usize, rng: &mut Rng) -> String {
+    assert!((2..=26 * 26).contains(&valves), "Invalid valve count");
+
+    let mut labels = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| format!("{}{}", a as char, b as char)))
+        .skip(1)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut labels);
+    labels.truncate(valves - 1);
+    labels.insert(0, String::from("AA"));
+
+    // A random spanning tree keeps every valve reachable, then add a few more tunnels.
+    let mut tunnels = vec![Vec::new(); valves];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for valve in 1..valves {
+        connect(valve, rng.index(valve));
+    }
+    for _ in 0..valves / 2 {
+        connect(rng.index(valves), rng.index(valves));
+    }
+
+    let mut lines = tunnels
+        .iter()
+        .enumerate()
+        .map(|(valve, tunnels)| {
+            let flow_rate = if valve > 0 && rng.one_in(4) {
+                rng.range(1..=25)
+            } else {
+                0
+            };
+            let leads = tunnels
+                .iter()
+                .map(|&t| labels[t].as_str())
+                .collect::<Vec<_>>();
+            match leads.as_slice() {
+                [lead] => format!(
+                    "Valve {} has flow rate={flow_rate}; tunnel leads to valve {lead}",
+                    labels[valve]
+                ),
+                _ => format!(
+                    "Valve {} has flow rate={flow_rate}; tunnels lead to valves {}",
+                    labels[valve],
+                    leads.join(", ")
+                ),
+            }
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+/// An encrypted file of `numbers` numbers, at least 2, a single one being 0.
+pub fn day20(numbers: usize, rng: &mut Rng) -> String {
+    assert!(numbers >= 2, "There must be at least 2 numbers");
+
+    let zero = rng.index(numbers);
+    let mut file = String::new();
+    for n in 0..numbers {
+        let number = if n == zero {
+            0
+        } else {
+            loop {
+                let number = rng.range(-10_000..=10_000);
+                if number != 0 {
+                    break number;
+                }
+            }
+        };
+        writeln!(file, "{number}").unwrap();
+    }
+    file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{day11, day14, day15, day16, day20, solution::SOLUTIONS};
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let numbers = (0..1000).map(|_| rng.range(-3..=3)).collect::<HashSet<_>>();
+        assert_eq!(numbers, (-3..=3).collect());
+        assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn test_generated_inputs_parse() {
+        for seed in 0..20 {
+            let mut rng = Rng::new(seed);
+            let monkeys = day11::parse_input_a(&day11(8, &mut rng)).unwrap();
+            assert_eq!(monkeys.len(), 8);
+            day14::parse_input_a(&day14(20, &mut rng)).unwrap();
+            assert_eq!(
+                day15::parse_input_a(&day15(10, &mut rng)).unwrap().len(),
+                10
+            );
+            let valves = day16(30, &mut rng);
+            assert_eq!(day16::parse_input_a(&valves).unwrap().len(), 30);
+            assert!(valves.contains("Valve AA has flow rate=0"));
+            let numbers = day20::parse_input_a(&day20(50, &mut rng)).unwrap();
+            assert_eq!(numbers.iter().filter(|&&n| n == 0).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        let mut rng = Rng::new(2022);
+        for (day, size) in [(14, 20), (20, 100)] {
+            let input = generate(day, size, &mut rng).unwrap();
+            for solution in SOLUTIONS.iter().filter(|s| s.day() == day) {
+                assert!(solution.run(&input).is_ok(), "{} failed", solution.name());
+            }
+        }
+    }
+}
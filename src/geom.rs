@@ -0,0 +1,420 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// The integer types usable as point coordinates.
+pub trait Number:
+    Copy
+    + Debug
+    + Default
+    + Display
+    + Eq
+    + Hash
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs_diff(self, other: Self) -> Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+/// The signed integer types, for points which can move in any direction.
+pub trait Signed: Number + Neg<Output = Self> {
+    fn signum(self) -> Self;
+}
+
+macro_rules! number {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs_diff(self, other: Self) -> Self {
+                    self.max(other) - self.min(other)
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! signed {
+    ($($t:ty),*) => {
+        $(
+            impl Signed for $t {
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+            }
+        )*
+    };
+}
+
+number!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+signed!(i8, i16, i32, i64, isize);
+
+/// A point, or a vector, on a plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Number> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    /// Distance when only moving horizontally or vertically.
+    ///
+    /// https://en.wikipedia.org/wiki/Taxicab_geometry
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance when moving diagonally is allowed too.
+    ///
+    /// https://en.wikipedia.org/wiki/Chebyshev_distance
+    pub fn chebyshev(&self, other: &Self) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Offset the point, unless a coordinate would overflow.
+    pub fn checked_offset(&self, dx: i8, dy: i8) -> Option<Self> {
+        let shift = |v: T, d: i8| match d {
+            -1 => v.checked_sub(T::ONE),
+            1 => v.checked_add(T::ONE),
+            _ => Some(v),
+        };
+        Some(Point2::new(shift(self.x, dx)?, shift(self.y, dy)?))
+    }
+
+    /// The 4 orthogonal neighbours, clockwise from the one above, skipping those which
+    /// would overflow.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| point.checked_offset(dx, dy))
+    }
+
+    /// The 8 neighbours, clockwise from the top left one, skipping those which would
+    /// overflow.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+        ]
+        .into_iter()
+        .filter_map(move |(dx, dy)| point.checked_offset(dx, dy))
+    }
+}
+
+impl<T: Signed> Point2<T> {
+    /// The vector with each coordinate replaced by its sign.
+    pub fn signum(&self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Move by one unit in the given direction.
+    pub fn step(&self, direction: Direction) -> Self {
+        *self + direction.delta()
+    }
+}
+
+impl<T: Number> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Number> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Number> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Signed> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Number> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Number> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{},{}]", self.x, self.y)
+    }
+}
+
+/// A point, or a vector, in space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Number> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// The 6 neighbours sharing a face, skipping those which would overflow.
+    pub fn neighbours6(&self) -> impl Iterator<Item = Self> {
+        let (x, y, z) = (self.x, self.y, self.z);
+        [
+            x.checked_sub(T::ONE).map(|x| Point3::new(x, y, z)),
+            x.checked_add(T::ONE).map(|x| Point3::new(x, y, z)),
+            y.checked_sub(T::ONE).map(|y| Point3::new(x, y, z)),
+            y.checked_add(T::ONE).map(|y| Point3::new(x, y, z)),
+            z.checked_sub(T::ONE).map(|z| Point3::new(x, y, z)),
+            z.checked_add(T::ONE).map(|z| Point3::new(x, y, z)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+impl<T: Number> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Number> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Number> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Signed> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Number> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Number> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{},{},{}]", self.x, self.y, self.z)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Clockwise,
+    Counterclockwise,
+}
+
+/// A direction on a map, `y` growing downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub fn reverse(&self) -> Self {
+        match self {
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Up => Direction::Down,
+        }
+    }
+
+    pub fn turn(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::Clockwise => match self {
+                Direction::Down => Direction::Left,
+                Direction::Left => Direction::Up,
+                Direction::Up => Direction::Right,
+                Direction::Right => Direction::Down,
+            },
+            Turn::Counterclockwise => match self {
+                Direction::Down => Direction::Right,
+                Direction::Right => Direction::Up,
+                Direction::Up => Direction::Left,
+                Direction::Left => Direction::Down,
+            },
+        }
+    }
+
+    /// The unit vector pointing in this direction.
+    pub fn delta<T: Signed>(&self) -> Point2<T> {
+        match self {
+            Direction::Up => Point2::new(T::ZERO, -T::ONE),
+            Direction::Right => Point2::new(T::ONE, T::ZERO),
+            Direction::Down => Point2::new(T::ZERO, T::ONE),
+            Direction::Left => Point2::new(-T::ONE, T::ZERO),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_point2() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(4, 2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(b - a, Point2::new(3, 4));
+        assert_eq!(-a * 2, Point2::new(-2, 4));
+        assert_eq!((b - a).signum(), Point2::new(1, 1));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(Point2::new(3u32, 9).manhattan(&Point2::new(5, 1)), 10);
+        assert_eq!(a.to_string(), "[1,-2]");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let origin = Point2::new(0usize, 0);
+        assert_eq!(
+            origin.neighbours4().collect::<Vec<_>>(),
+            vec![Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(origin.neighbours8().count(), 3);
+        assert_eq!(Point2::new(0i64, 0).neighbours8().count(), 8);
+        assert_eq!(Point3::new(0u8, 1, 255).neighbours6().count(), 4);
+    }
+
+    #[test]
+    fn test_point3() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(10, 20, 30);
+        assert_eq!(a + b, Point3::new(11, 22, 33));
+        assert_eq!(b - a, Point3::new(9, 18, 27));
+        assert_eq!(a.manhattan(&b), 54);
+        assert_eq!(a.chebyshev(&b), 27);
+        assert_eq!(
+            a.neighbours6().map(|n| n - a).collect::<Vec<_>>(),
+            vec![
+                Point3::new(-1, 0, 0),
+                Point3::new(1, 0, 0),
+                Point3::new(0, -1, 0),
+                Point3::new(0, 1, 0),
+                Point3::new(0, 0, -1),
+                Point3::new(0, 0, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_direction() {
+        let mut direction = Direction::Up;
+        for expected in [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ] {
+            direction = direction.turn(Turn::Clockwise);
+            assert_eq!(direction, expected);
+            assert_eq!(
+                direction.turn(Turn::Counterclockwise).turn(Turn::Clockwise),
+                direction
+            );
+        }
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Point2::new(2, 2).step(Direction::Up), Point2::new(2, 1));
+        assert_eq!(
+            Direction::Down.delta::<i32>(),
+            -Direction::Down.reverse().delta::<i32>()
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    error::{parse_lines, AocError, LineError, Result},
+    geom::Point2,
+};
+
+/// Position of a cell in a [`Grid`], `y` growing downwards.
+pub type Position = Point2<usize>;
+
+/// A rectangular grid, stored row by row.
+///
+/// Ragged maps, whose rows have different lengths, are stored as a `Grid<Option<T>>`
+/// padded with `None`, see [`Grid::parse_ragged`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of the given size, every cell holding `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Build a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map(|row| row.len()).unwrap_or_default();
+        if width == 0 {
+            return Err(AocError::parse(1, "The grid is empty"));
+        }
+        if let Some(n) = rows.iter().position(|row| row.len() != width) {
+            return Err(AocError::parse(n + 1, "The grid is not rectangular"));
+        }
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parse a rectangular map, one character per cell.
+    ///
+    /// ```
+    /// use aoc2022::grid::Grid;
+    ///
+    /// let grid = Grid::parse_chars("12\n34\n", |c| c.to_digit(10).ok_or("Not a digit")).unwrap();
+    /// assert_eq!(grid[(1, 0).into()], 2);
+    /// assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 4]);
+    /// assert!(Grid::parse_chars("12\n3\n", |c| c.to_digit(10).ok_or("Not a digit")).is_err());
+    /// ```
+    pub fn parse_chars<E, F>(input: &str, parse: F) -> Result<Self>
+    where
+        E: LineError,
+        F: Fn(char) -> Result<T, E>,
+    {
+        Grid::from_rows(parse_lines(input, |l| {
+            l.chars().map(&parse).collect::<Result<Vec<_>, _>>()
+        })?)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.y * self.width + position.x])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.y * self.width + position.x])
+    }
+
+    /// The cells of row `y`, from left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell matching the predicate, row by row.
+    pub fn position<P>(&self, predicate: P) -> Option<Position>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.iter().find(|(_, t)| predicate(t)).map(|(p, _)| p)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The up to 4 orthogonal neighbours of a position which are within the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        position.neighbours4().filter(|&p| self.contains(p))
+    }
+
+    /// The up to 8 neighbours of a position, diagonals included, which are within the grid.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        position.neighbours8().filter(|&p| self.contains(p))
+    }
+
+    /// Render the grid with one character per cell.
+    pub fn render<F>(&self, f: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            s.extend(row.iter().map(&f));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T> Grid<Option<T>> {
+    /// Parse a map whose rows may have different lengths, shorter rows being padded with
+    /// `None`, like the cells for which `parse` returns `None`.
+    ///
+    /// ```
+    /// use aoc2022::grid::Grid;
+    ///
+    /// let grid = Grid::parse_ragged("  #\n#.\n", |c| match c {
+    ///     ' ' => Ok(None),
+    ///     '#' | '.' => Ok(Some(c)),
+    ///     _ => Err("Invalid tile"),
+    /// })
+    /// .unwrap();
+    /// assert_eq!((grid.width(), grid.height()), (3, 2));
+    /// assert_eq!(grid.render(|c| c.unwrap_or('_')), "__#\n#._\n");
+    /// ```
+    pub fn parse_ragged<E, F>(input: &str, parse: F) -> Result<Self>
+    where
+        T: Clone,
+        E: LineError,
+        F: Fn(char) -> Result<Option<T>, E>,
+    {
+        let mut rows = parse_lines(input, |l| {
+            l.chars().map(&parse).collect::<Result<Vec<_>, _>>()
+        })?;
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+        for row in rows.iter_mut() {
+            row.resize(width, None);
+        }
+        Grid::from_rows(rows)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, Point2 { x, y }: Position) -> &Self::Output {
+        assert!(x < self.width, "Column {x} is out of the grid");
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, Point2 { x, y }: Position) -> &mut Self::Output {
+        assert!(x < self.width, "Column {x} is out of the grid");
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// An unbounded grid only storing its occupied cells, for maps growing in any direction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T>(HashMap<Point2<i64>, T>);
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid(HashMap::new())
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid::default()
+    }
+
+    pub fn get(&self, position: Point2<i64>) -> Option<&T> {
+        self.0.get(&position)
+    }
+
+    pub fn contains(&self, position: Point2<i64>) -> bool {
+        self.0.contains_key(&position)
+    }
+
+    /// Set a cell, returning its previous value.
+    pub fn insert(&mut self, position: Point2<i64>, value: T) -> Option<T> {
+        self.0.insert(position, value)
+    }
+
+    pub fn remove(&mut self, position: Point2<i64>) -> Option<T> {
+        self.0.remove(&position)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The occupied cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<i64>, &T)> {
+        self.0.iter().map(|(&p, t)| (p, t))
+    }
+
+    /// The top left and bottom right corners of the occupied cells, if any.
+    pub fn bounds(&self) -> Option<(Point2<i64>, Point2<i64>)> {
+        let mut positions = self.0.keys();
+        let &first = positions.next()?;
+        Some(positions.fold((first, first), |(min, max), p| {
+            (
+                Point2::new(min.x.min(p.x), min.y.min(p.y)),
+                Point2::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
+    /// Render the occupied area with one character per cell, `empty` for the free ones.
+    ///
+    /// ```
+    /// use aoc2022::grid::SparseGrid;
+    ///
+    /// let mut grid = SparseGrid::new();
+    /// grid.insert((-1, 5).into(), '#');
+    /// grid.insert((1, 6).into(), 'o');
+    /// assert_eq!(grid.render('.', |&c| c), "#..\n..o\n");
+    /// ```
+    pub fn render<F>(&self, empty: char, f: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        let mut s = String::new();
+        if let Some((min, max)) = self.bounds() {
+            for y in min.y..=max.y {
+                s.extend((min.x..=max.x).map(|x| self.get(Point2::new(x, y)).map_or(empty, &f)));
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+impl<T> FromIterator<(Point2<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2<i64>, T)>>(iter: I) -> Self {
+        SparseGrid(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RAW_INPUT: &str = "abc
+def
+ghi
+";
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::parse_chars(RAW_INPUT, Ok::<_, String>).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cfi");
+        assert_eq!(grid.get(Point2::new(3, 0)), None);
+        assert_eq!(grid.position(|&c| c == 'h'), Some(Point2::new(1, 2)));
+        assert_eq!(grid.to_string(), RAW_INPUT);
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).render(|&c| c),
+            RAW_INPUT.to_uppercase()
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(
+            grid.neighbours4(Point2::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4(Point2::new(1, 1)).collect::<Vec<_>>(),
+            vec![Point2::new(1, 0), Point2::new(2, 1), Point2::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours8(Point2::new(1, 0)).count(), 5);
+        assert_eq!(grid.neighbours8(Point2::new(2, 1)).count(), 3);
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point2::new(2, -1), 1);
+        grid.insert(Point2::new(-3, 4), 2);
+        assert_eq!(
+            grid.bounds(),
+            Some((Point2::new(-3, -1), Point2::new(2, 4)))
+        );
+        assert_eq!(grid.get(Point2::new(2, -1)), Some(&1));
+        assert!(!grid.contains(Point2::new(0, 0)));
+        assert_eq!(grid.len(), 2);
+    }
+}
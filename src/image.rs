@@ -0,0 +1,384 @@
+use std::{
+    fs,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    day08, day12, day14, day17, day22,
+    error::Result,
+    geom::Point2,
+    grid::Grid,
+    params::Params,
+    solution::Part,
+    visualize::{Frame, NullVisualizer, Visualizer},
+};
+
+/// A pixel color, as red, green and blue intensities.
+pub type Rgb = [u8; 3];
+
+/// The puzzles we can draw the final state of.
+pub const IMAGES: &[(u8, Part)] = &[
+    (8, Part::A),
+    (8, Part::B),
+    (12, Part::A),
+    (14, Part::A),
+    (14, Part::B),
+    (17, Part::A),
+    (22, Part::A),
+];
+
+/// How the pixels of a Netpbm image are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// One byte per intensity, `P5` and `P6` images.
+    #[default]
+    Raw,
+    /// Decimal intensities, `P2` and `P3` images readable as text.
+    Plain,
+}
+
+/// A picture written as a PGM image when gray, as a PPM one otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Image {
+    Gray(Grid<u8>),
+    Color(Grid<Rgb>),
+}
+
+impl Image {
+    /// Color a text picture, like the frames of a simulation, one pixel per character.
+    ///
+    /// Shorter rows are padded with spaces, see [`color`] for the colors.
+    pub fn from_picture(picture: &str) -> Self {
+        let width = picture
+            .lines()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or_default();
+        let mut pixels = Grid::new(width, picture.lines().count(), color(' '));
+        for (y, row) in picture.lines().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                pixels[Point2::new(x, y)] = color(c);
+            }
+        }
+        Image::Color(pixels)
+    }
+
+    pub fn width(&self) -> usize {
+        match self {
+            Image::Gray(pixels) => pixels.width(),
+            Image::Color(pixels) => pixels.width(),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        match self {
+            Image::Gray(pixels) => pixels.height(),
+            Image::Color(pixels) => pixels.height(),
+        }
+    }
+
+    /// Enlarge the image, each pixel becoming a square of `factor` pixels a side.
+    pub fn scale(&self, factor: usize) -> Self {
+        fn scale<T: Copy + Default>(pixels: &Grid<T>, factor: usize) -> Grid<T> {
+            let mut scaled = Grid::new(
+                pixels.width() * factor,
+                pixels.height() * factor,
+                T::default(),
+            );
+            for p in scaled.positions().collect::<Vec<_>>() {
+                scaled[p] = pixels[Point2::new(p.x / factor, p.y / factor)];
+            }
+            scaled
+        }
+
+        match self {
+            Image::Gray(pixels) => Image::Gray(scale(pixels, factor)),
+            Image::Color(pixels) => Image::Color(scale(pixels, factor)),
+        }
+    }
+
+    /// The extension of the image files, `pgm` or `ppm`.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Image::Gray(_) => "pgm",
+            Image::Color(_) => "ppm",
+        }
+    }
+
+    /// Write the image in the Netpbm format.
+    pub fn write<W: Write>(&self, mut output: W, encoding: Encoding) -> io::Result<()> {
+        let intensities = match self {
+            Image::Gray(pixels) => pixels.iter().map(|(_, &v)| v).collect::<Vec<_>>(),
+            Image::Color(pixels) => pixels.iter().flat_map(|(_, &rgb)| rgb).collect(),
+        };
+        let magic = match (self, encoding) {
+            (Image::Gray(_), Encoding::Raw) => "P5",
+            (Image::Color(_), Encoding::Raw) => "P6",
+            (Image::Gray(_), Encoding::Plain) => "P2",
+            (Image::Color(_), Encoding::Plain) => "P3",
+        };
+        writeln!(output, "{magic}\n{} {}\n255", self.width(), self.height())?;
+        match encoding {
+            Encoding::Raw => output.write_all(&intensities)?,
+            // The lines of plain images should not be longer than 70 characters.
+            Encoding::Plain => {
+                for line in intensities.chunks(17) {
+                    let line = line.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                    writeln!(output, "{}", line.join(" "))?;
+                }
+            }
+        }
+        output.flush()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P, encoding: Encoding) -> io::Result<()> {
+        self.write(BufWriter::new(fs::File::create(path)?), encoding)
+    }
+}
+
+/// The color of a character of a text picture.
+///
+/// Walls and rocks are gray, sand is yellow, the falling rocks and the rope are red, the
+/// trail of the traveler is green, and the void is black.
+pub fn color(c: char) -> Rgb {
+    match c {
+        ' ' => [0, 0, 0],
+        '.' => [24, 24, 32],
+        '#' => [128, 128, 128],
+        '|' | '-' | '+' => [80, 80, 80],
+        'o' => [230, 190, 80],
+        '@' | 'H' => [220, 60, 50],
+        'T' => [240, 140, 120],
+        's' => [255, 255, 255],
+        '^' | '>' | 'v' | '<' => [80, 200, 110],
+        _ => [200, 200, 200],
+    }
+}
+
+/// Draw the final state of a puzzle, if it is one of the [known ones](IMAGES).
+pub fn draw(day: u8, part: Part, input: &str, params: &Params) -> Option<Result<Image>> {
+    let image = match (day, part) {
+        (8, Part::A) => day08::parse_input_a(input).map(|trees| tree_heights(&trees)),
+        (8, Part::B) => day08::parse_input_a(input).map(|trees| scenic_scores(&trees)),
+        (12, Part::A) => day12::parse_input_a(input).and_then(|squares| heightmap(&squares)),
+        (14, part) => day14::parse_input_a(input).and_then(|mut cave| {
+            match part {
+                Part::A => day14::process_input_a(&mut cave, &mut NullVisualizer)?,
+                Part::B => day14::process_input_b(&mut cave, &mut NullVisualizer)?,
+            };
+            Ok(Image::from_picture(&cave.to_string()))
+        }),
+        (17, Part::A) => day17::parse_input_a(input).and_then(|motions| {
+            let chamber = day17::build_tower(&motions, params.day17.rocks, &mut NullVisualizer)?;
+            Ok(Image::from_picture(
+                &chamber.render(None, chamber.highest_rock()),
+            ))
+        }),
+        (22, Part::A) => day22::parse_input_a(input).and_then(|(jungle, motions)| {
+            let (_, trail) = day22::walk(&jungle, &motions, &mut NullVisualizer)?;
+            Ok(Image::from_picture(&jungle.render_trail(&trail)))
+        }),
+        _ => return None,
+    };
+    Some(image)
+}
+
+/// The trees of day 8, the higher the lighter.
+pub fn tree_heights(trees: &Grid<u8>) -> Image {
+    Image::Gray(trees.map(|&height| height * 28))
+}
+
+/// The scenic scores of the trees of day 8, on a logarithmic scale to tell the low
+/// scores apart, the best ones being white.
+pub fn scenic_scores(trees: &Grid<u8>) -> Image {
+    let mut scores = Grid::new(trees.width(), trees.height(), 0.0);
+    for p in trees.positions() {
+        scores[p] = (day08::scenic_score(trees, p.y, p.x) as f64).ln_1p();
+    }
+    let best = scores.iter().map(|(_, &s)| s).fold(0.0, f64::max);
+    Image::Gray(scores.map(|&s| {
+        if best > 0.0 {
+            (s / best * 255.0) as u8
+        } else {
+            0
+        }
+    }))
+}
+
+/// The heightmap of day 12, the higher the lighter, with a shortest path in red from the
+/// start, in green, to the best signal, in blue.
+pub fn heightmap(squares: &Grid<day12::Square>) -> Result<Image> {
+    let (nodes, graph, start, end) = day12::build_graph(squares)?;
+    let path = day12::shortest_path(squares, &nodes, &graph, start, end)?;
+    let mut pixels = squares.map(|square| {
+        let v = 30 + (square.weight() - b'a') * 8;
+        [v, v, v]
+    });
+    for &p in &path {
+        pixels[p] = [220, 50, 50];
+    }
+    pixels[start] = [50, 200, 80];
+    pixels[end] = [60, 110, 240];
+    Ok(Image::Color(pixels))
+}
+
+/// Writes each frame of a simulation to a numbered image file, i.e. `day14a-000001.ppm`.
+pub struct FrameWriter {
+    dir: PathBuf,
+    prefix: String,
+    every: usize,
+    scale: usize,
+    encoding: Encoding,
+    count: usize,
+    written: usize,
+    error: Option<io::Error>,
+}
+
+impl FrameWriter {
+    /// Write every `every`-th frame into `dir`, which gets created, each pixel scaled by
+    /// `scale`.
+    pub fn new<P: Into<PathBuf>>(
+        dir: P,
+        prefix: &str,
+        every: usize,
+        scale: usize,
+        encoding: Encoding,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(FrameWriter {
+            dir,
+            prefix: prefix.to_string(),
+            every: every.max(1),
+            scale: scale.max(1),
+            encoding,
+            count: 0,
+            written: 0,
+            error: None,
+        })
+    }
+
+    /// The number of frames written, or the first error which stopped the writing.
+    pub fn finish(self) -> io::Result<usize> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.written),
+        }
+    }
+
+    fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        let image = Image::from_picture(&frame.picture).scale(self.scale);
+        let name = format!("{}-{:06}.{}", self.prefix, self.count, image.extension());
+        image.save(self.dir.join(name), self.encoding)?;
+        self.written += 1;
+        Ok(())
+    }
+}
+
+impl Visualizer for FrameWriter {
+    fn enabled(&self) -> bool {
+        self.error.is_none()
+    }
+
+    fn show(&mut self, frame: Frame) {
+        if let Err(e) = self.write(&frame) {
+            self.error = Some(e);
+        }
+    }
+
+    /// The skipped frames are not even drawn.
+    fn draw(&mut self, frame: &dyn Fn() -> Frame) {
+        self.count += 1;
+        if self.enabled() && (self.count - 1).is_multiple_of(self.every) {
+            self.show(frame());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_write() {
+        let mut output = Vec::new();
+        let image = Image::Gray(Grid::from_rows(vec![vec![0, 255], vec![9, 28]]).unwrap());
+        image.write(&mut output, Encoding::Plain).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "P2\n2 2\n255\n0 255 9 28\n"
+        );
+
+        let mut output = Vec::new();
+        image.scale(2).write(&mut output, Encoding::Raw).unwrap();
+        assert_eq!(output[..11], *b"P5\n4 4\n255\n");
+        assert_eq!(
+            output[11..],
+            [0, 0, 255, 255, 0, 0, 255, 255, 9, 9, 28, 28, 9, 9, 28, 28]
+        );
+
+        let mut output = Vec::new();
+        let image = Image::from_picture("#o\n.\n");
+        image.write(&mut output, Encoding::Plain).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "P3\n2 2\n255\n128 128 128 230 190 80 24 24 32 0 0 0\n"
+        );
+
+        let mut output = Vec::new();
+        let image = Image::Gray(Grid::new(30, 1, 100));
+        image.write(&mut output, Encoding::Plain).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.lines().all(|line| line.len() <= 70));
+        assert_eq!(output.matches("100").count(), 30);
+    }
+
+    #[test]
+    fn test_draw() {
+        let trees = "30373\n25512\n65332\n33549\n35390\n";
+        let image = draw(8, Part::B, trees, &Params::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (image.width(), image.height(), image.extension()),
+            (5, 5, "pgm")
+        );
+        let Image::Gray(pixels) = image else {
+            panic!("The scenic scores are gray");
+        };
+        // The best tree has a scenic score of 8.
+        assert_eq!(pixels[Point2::new(2, 3)], 255);
+        assert_eq!(pixels[Point2::new(0, 0)], 0);
+
+        let cave = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        let image = draw(14, Part::A, cave, &Params::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (image.width(), image.height(), image.extension()),
+            (10, 8, "ppm")
+        );
+        assert!(draw(1, Part::A, "", &Params::default()).is_none());
+    }
+
+    #[test]
+    fn test_frame_writer() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-frames-{}", std::process::id()));
+        let mut writer = FrameWriter::new(&dir, "test", 2, 1, Encoding::Raw).unwrap();
+        for n in 0..5 {
+            writer.draw(&|| Frame::new(n.to_string(), "#."));
+        }
+        assert_eq!(writer.finish().unwrap(), 3);
+        let mut files = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(
+            files,
+            ["test-000001.ppm", "test-000003.ppm", "test-000005.ppm"]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
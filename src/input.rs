@@ -0,0 +1,102 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Directory holding the puzzle inputs, relative to the crate root.
+pub const ASSETS_DIR: &str = "assets";
+
+/// Where the puzzle input of a solution is read from.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum Input {
+    /// The `assets/dayNN.txt` file of the day.
+    #[default]
+    Default,
+    /// The `dayNN.txt` file of the day within another directory.
+    Dir(PathBuf),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl From<&str> for Input {
+    fn from(item: &str) -> Self {
+        match item {
+            "-" => Input::Stdin,
+            _ => Input::Path(PathBuf::from(item)),
+        }
+    }
+}
+
+impl Input {
+    /// Path of the file to read for a specific day, if any.
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            Input::Default => Some(default_path(day)),
+            Input::Dir(dir) => Some(input_path(dir, day)),
+            Input::Path(path) => Some(path.clone()),
+            Input::Stdin => None,
+        }
+    }
+
+    /// Read the input, errors mentioning the path of the file which could not be read.
+    pub fn read(&self, day: u8) -> io::Result<String> {
+        match self.path(day) {
+            Some(path) => fs::read_to_string(&path).map_err(|e| {
+                io::Error::new(e.kind(), format!("Cannot read {}: {e}", path.display()))
+            }),
+            None => {
+                let mut data = String::new();
+                io::stdin().read_to_string(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+}
+
+/// Path of the input file checked in for a specific day.
+///
+/// ```
+/// use aoc2022::input::default_path;
+///
+/// assert_eq!(default_path(7).to_str(), Some("assets/day07.txt"));
+/// ```
+pub fn default_path(day: u8) -> PathBuf {
+    input_path(Path::new(ASSETS_DIR), day)
+}
+
+/// Path of the input file of a specific day within a directory.
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day:02}.txt"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_from_str() {
+        assert_eq!(Input::from("-"), Input::Stdin);
+        assert_eq!(
+            Input::from("other/day07.txt"),
+            Input::Path(PathBuf::from("other/day07.txt"))
+        );
+    }
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(
+            Input::Default.path(1),
+            Some(PathBuf::from("assets/day01.txt"))
+        );
+        assert_eq!(
+            Input::Path(PathBuf::from("mine.txt")).path(1),
+            Some(PathBuf::from("mine.txt"))
+        );
+        assert_eq!(
+            Input::Dir(PathBuf::from("inputs")).path(1),
+            Some(PathBuf::from("inputs/day01.txt"))
+        );
+        assert_eq!(Input::Stdin.path(1), None);
+    }
+}
@@ -1,5 +1,17 @@
-use std::{fs, path::Path, str::FromStr};
+use std::{fmt::Display, fs, path::Path, str::FromStr};
 
+use error::{AocError, BadLine};
+use params::Params;
+pub use solution::{Answer, Part};
+
+#[cfg(feature = "cli")]
+pub mod answers;
+#[cfg(feature = "cli")]
+pub mod bench;
+#[cfg(feature = "cli")]
+pub mod config;
+#[cfg(feature = "cli")]
+pub mod crosscheck;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -22,6 +34,65 @@ pub mod day19;
 pub mod day20;
 pub mod day21;
 pub mod day22;
+pub mod error;
+#[cfg(feature = "cli")]
+pub mod fetch;
+pub mod generate;
+pub mod geom;
+pub mod grid;
+#[cfg(feature = "cli")]
+pub mod image;
+#[cfg(feature = "cli")]
+pub mod input;
+pub mod params;
+#[cfg(feature = "cli")]
+pub mod pool;
+#[cfg(feature = "cli")]
+pub mod runner;
+#[cfg(feature = "cli")]
+pub mod samples;
+#[cfg(feature = "cli")]
+pub mod scaffold;
+pub mod solution;
+pub mod visualize;
+#[cfg(feature = "cli")]
+pub mod watch;
+
+/// Solve a puzzle from the text of its input.
+///
+/// Unlike the rest of the crate, it reads no file and takes no time measurement, so that
+/// it can be embedded anywhere, wasm32 builds included. A malformed puzzle input is
+/// reported as an error rather than a panic.
+///
+/// ```
+/// use aoc2022::{error::AocError, solve, Answer, Part};
+///
+/// let input = "1\n2\n-3\n3\n-2\n0\n4\n";
+/// assert_eq!(solve(20, Part::A, input).unwrap(), Answer::Int(3));
+/// assert!(matches!(solve(20, Part::A, "1\nx\n"), Err(AocError::Parse { line: 2, .. })));
+/// assert!(matches!(solve(19, Part::A, input), Err(AocError::Unsolved { day: 19, .. })));
+/// ```
+pub fn solve(day: u8, part: Part, input: &str) -> Result<Answer, AocError> {
+    solve_with(day, part, input, &Params::default())
+}
+
+/// Solve a puzzle like [`solve`], with other parameters than the ones of its text.
+///
+/// ```
+/// use aoc2022::{day15::Scan, params::Params, solve_with, Answer, Part};
+///
+/// let input = "Sensor at x=8, y=7: closest beacon is at x=2, y=10\n";
+/// let params = Params {
+///     day15: Scan { row: 10 },
+///     ..Params::default()
+/// };
+/// assert_eq!(solve_with(15, Part::A, input, &params).unwrap(), Answer::Int(12));
+/// ```
+pub fn solve_with(day: u8, part: Part, input: &str, params: &Params) -> Result<Answer, AocError> {
+    solution::find(day, part)
+        .ok_or(AocError::Unsolved { day, part })?
+        .solve_with(input, params)
+}
 
 pub fn read_input<P, T>(input: P) -> Vec<T>
 where
@@ -48,6 +119,38 @@ where
         .collect::<Vec<T>>()
 }
 
+/// Strict counterpart of [`read_input`], failing on IO errors and on any bad line.
+pub fn read_input_strict<P, T>(input: P) -> Result<Vec<T>, AocError>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let values = fs::read_to_string(input)?;
+    Ok(input_from_string_strict::<T>(&values)?)
+}
+
+/// Strict counterpart of [`input_from_string`], returning every line which could not be
+/// parsed instead of dropping it.
+///
+/// ```
+/// use aoc2022::input_from_string_strict;
+///
+/// assert_eq!(input_from_string_strict::<u8>("1\n6\n8\n"), Ok(vec![1, 6, 8]));
+///
+/// let bad_lines = input_from_string_strict::<u8>("1\nsix\n8\n256\n").unwrap_err();
+/// assert_eq!(bad_lines.len(), 2);
+/// assert_eq!((bad_lines[0].line, bad_lines[0].text.as_str()), (2, "six"));
+/// assert_eq!((bad_lines[1].line, bad_lines[1].text.as_str()), (4, "256"));
+/// ```
+pub fn input_from_string_strict<T>(input: &str) -> Result<Vec<T>, Vec<BadLine>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_items(input, input.lines())
+}
+
 pub fn read_input_sep<P, T>(input: P, separator: &str) -> Vec<T>
 where
     P: AsRef<Path>,
@@ -75,26 +178,82 @@ where
         .collect::<Vec<T>>()
 }
 
+/// Strict counterpart of [`read_input_sep`], failing on IO errors and on any bad item.
+pub fn read_input_sep_strict<P, T>(input: P, separator: &str) -> Result<Vec<T>, AocError>
+where
+    P: AsRef<Path>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let values = fs::read_to_string(input)?;
+    Ok(input_sep_from_string_strict::<T>(&values, separator)?)
+}
+
+/// Strict counterpart of [`input_sep_from_string`], returning every item which could not be
+/// parsed along with the line it starts on.
+///
+/// ```
+/// use aoc2022::input_sep_from_string_strict;
+///
+/// assert_eq!(input_sep_from_string_strict::<u8>("1,6\n", ","), Ok(vec![1, 6]));
+///
+/// let bad_lines = input_sep_from_string_strict::<u8>("1\n\n2\n\nx\n", "\n\n").unwrap_err();
+/// assert_eq!((bad_lines[0].line, bad_lines[0].text.as_str()), (5, "x"));
+/// ```
+pub fn input_sep_from_string_strict<T>(input: &str, separator: &str) -> Result<Vec<T>, Vec<BadLine>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_items(input, input.trim().split(separator))
+}
+
+/// Parse items borrowed from `input`, numbering the bad ones after the line they start on.
+fn parse_items<'a, T>(
+    input: &str,
+    items: impl Iterator<Item = &'a str>,
+) -> Result<Vec<T>, Vec<BadLine>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let mut values = Vec::new();
+    let mut bad_lines = Vec::new();
+    for item in items {
+        match item.parse::<T>() {
+            Ok(value) => values.push(value),
+            Err(e) => {
+                let offset = item.as_ptr() as usize - input.as_ptr() as usize;
+                bad_lines.push(BadLine {
+                    line: input[..offset].matches('\n').count() + 1,
+                    text: item.to_string(),
+                    message: e.to_string(),
+                })
+            }
+        }
+    }
+    if bad_lines.is_empty() {
+        Ok(values)
+    } else {
+        Err(bad_lines)
+    }
+}
+
 // Create a FromStr implementation for a specific type.
 //
-// This macros expects the following items to be setup before use:
-//   - `use std::str::FromStr` must be in scope
-//   - the type must implement a `parse` function with the following signature
-//       `pub fn parse(i: &str) -> IResult<&str, __type__>`
+// The whole string must be consumed, trailing whitespace aside, and failures are reported
+// as a `SyntaxError` with the line and column where parsing stopped.
+//
+// This macros expects the type to implement a `parse` function with the following signature
+//   `pub fn parse(i: &str) -> IResult<&str, __type__>`
 #[macro_export]
 macro_rules! nomstr {
     ($a:ident) => {
-        impl FromStr for $a {
-            type Err = Error<String>;
+        impl ::std::str::FromStr for $a {
+            type Err = $crate::error::SyntaxError;
 
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                match $a::parse(s).finish() {
-                    Ok((_, item)) => Ok(item),
-                    Err(Error { input, code }) => Err(Error {
-                        input: input.to_string(),
-                        code,
-                    }),
-                }
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                $crate::error::parse_all(s, $a::parse)
             }
         }
     };
@@ -1,75 +1,541 @@
-use aoc2022::day01::{day01a, day01b};
-use aoc2022::day02::{day02a, day02b};
-use aoc2022::day03::{day03a, day03b};
-use aoc2022::day04::{day04a, day04b};
-use aoc2022::day05::{day05a, day05b};
-use aoc2022::day06::{day06a, day06b};
-use aoc2022::day07::{day07a, day07b};
-use aoc2022::day08::{day08a, day08b};
-use aoc2022::day09::{day09a, day09b};
-use aoc2022::day10::{day10a, day10b};
-use aoc2022::day11::{day11a, day11b};
-use aoc2022::day12::{day12a, day12b};
-use aoc2022::day13::{day13a, day13b};
-use aoc2022::day14::{day14a, day14b};
-use aoc2022::day15::{day15a, day15b};
-use aoc2022::day16::{day16a, day16b};
-use aoc2022::day17::{day17a, day17b};
-use aoc2022::day18::{day18a, day18b};
-use aoc2022::day19::{day19a, day19b};
-use aoc2022::day20::{day20a, day20b};
-use aoc2022::day21::{day21a, day21b};
-use aoc2022::day22::{day22a, day22b};
+use std::{fs, process};
+
+use log::{debug, LevelFilter};
+
+use aoc2022::{
+    answers::{KnownAnswers, Verdict},
+    bench::{bench, DEFAULT_RUNS, DEFAULT_WARMUP},
+    config::Config,
+    crosscheck::{search, Crosscheck},
+    error::{AocError, Result},
+    fetch::{Fetched, Fetcher},
+    generate::{default_size, generate, valid_sizes, Rng},
+    image::{draw, Encoding, FrameWriter},
+    input::Input,
+    params::WithParams,
+    pool::{self, render_matrix},
+    runner::{
+        default_jobs, render_json, render_table, render_verification, run_all, Elapsed, Format,
+        Report,
+    },
+    samples::{discover, render_samples, run_samples, SAMPLES_DIR},
+    scaffold::{Sample, Scaffold},
+    solution::{day_and_part, find_by_name, implementations, Solution, SOLUTIONS},
+    visualize::{visualize, AnsiVisualizer, Pace, DEFAULT_FPS},
+    watch::Watcher,
+};
+
+const USAGE: &str = "Usage: aoc2022 <dayNNx> [--input <path>|-] [--format text|json]
+       aoc2022 bench <dayNNx> [--input <path>|-] [--warmup <n>] [--runs <n>]
+       aoc2022 crosscheck <dayNNx> [--input <path>|-]
+       aoc2022 crosscheck <dayNNx> --generate [--size <n>] [--seed <n>] [--runs <n>]
+       aoc2022 fetch <day>
+       aoc2022 generate <day> [--size <n>] [--seed <n>]
+       aoc2022 image <dayNNx> [--input <path>|-] [--output <path>] [--scale <n>] [--plain]
+       aoc2022 frames <dayNNx> [--input <path>|-] [--output <dir>] [--every <n>] [--scale <n>]
+                      [--plain]
+       aoc2022 new-day <day> [--sample <path>] [--answer-a <answer>] [--answer-b <answer>]
+       aoc2022 run-all [--format text|json] [--jobs <n>]
+       aoc2022 verify [--format text|json] [--jobs <n>]
+       aoc2022 visualize <dayNNx> [--input <path>|-] [--fps <n>] [--step]
+       aoc2022 samples [<day>]
+       aoc2022 pool <day> <dir>
+       aoc2022 watch <day> [--input <path>]
+       aoc2022 list
+
+Options, before or after the command:
+  -i, --input <path>  Read the puzzle input from <path>, or from stdin with `-`.
+                      Defaults to assets/dayNN.txt.
+  -v, -vv             Log diagnostics to stderr, -vv being the most verbose.
+  -f, --format <fmt>  Print the results as `text`, the default, or as `json`, one
+                      object per line and per puzzle part.
+  -j, --jobs <n>      Number of threads solving the puzzles. Defaults to the number
+                      of cores.
+      --warmup <n>    Number of unrecorded runs before benchmarking. Defaults to 3.
+      --runs <n>      Number of recorded benchmark runs, or of generated inputs to
+                      cross-check. Defaults to 10.
+      --generate      Cross-check the implementations against generated inputs.
+      --size <n>      Size of a generated input, i.e. the number of monkeys for day 11.
+      --seed <n>      Seed of a generated input, the next ones being cross-checked
+                      after it. Defaults to a random one.
+      --fps <n>       Frames shown per second by `visualize`, 0 for as many as
+                      possible. Defaults to 30.
+      --step          Wait for Enter before showing the next frame, the input
+                      cannot then come from stdin.
+  -o, --output <path> Image written by `image`, `dayNNx.pgm` or `dayNNx.ppm` by default,
+                      or directory of the numbered images written by `frames`,
+                      `frames` by default.
+      --scale <n>     Size of the squares standing for the cells of the images.
+                      Defaults to 1.
+      --plain         Write the images as text, in the plain Netpbm format.
+      --every <n>     Only write one frame out of <n>. Defaults to 1.
+      --sample <path> Sample input the tests of a new day are run against.
+      --answer-a <answer>, --answer-b <answer>
+                      Expected answers to the sample input of a new day.
+
+The session token used by `fetch` is read from $AOC_SESSION, or from
+$XDG_CONFIG_HOME/aoc2022/session. $AOC_BASE_URL overrides the website URL.
+
+The defaults are read from aoc.toml, in the working directory or else in
+$XDG_CONFIG_HOME/aoc2022: `assets-dir`, `year`, `format`, `session-file`, and the
+numbers of the puzzle texts in the [day07], [day15] and [day17] tables.";
+
+#[derive(Debug)]
+struct Options {
+    command: Option<String>,
+    input: Input,
+    format: Format,
+    verbosity: usize,
+    jobs: usize,
+    warmup: usize,
+    runs: usize,
+    generate: bool,
+    fps: f64,
+    step: bool,
+    output: Option<String>,
+    scale: usize,
+    every: usize,
+    encoding: Encoding,
+    size: Option<usize>,
+    seed: Option<u64>,
+    sample: Option<String>,
+    answer_a: Option<String>,
+    answer_b: Option<String>,
+    positional: Vec<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            command: None,
+            input: Input::Default,
+            format: Format::Text,
+            verbosity: 0,
+            jobs: default_jobs(),
+            warmup: DEFAULT_WARMUP,
+            runs: DEFAULT_RUNS,
+            generate: false,
+            fps: DEFAULT_FPS,
+            step: false,
+            output: None,
+            scale: 1,
+            every: 1,
+            encoding: Encoding::Raw,
+            size: None,
+            seed: None,
+            sample: None,
+            answer_a: None,
+            answer_b: None,
+            positional: Vec::new(),
+        }
+    }
+}
+
+impl Options {
+    /// Parse the arguments, the configuration supplying the defaults they override.
+    ///
+    /// The options may come before or after the command, which is the first positional
+    /// argument.
+    fn parse(args: &[String], config: &Config) -> Result<Self, String> {
+        let mut options = Options {
+            input: config.input(),
+            format: config.format.unwrap_or_default(),
+            ..Options::default()
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("Missing value for `{arg}`"));
+            match arg.as_str() {
+                "-i" | "--input" => options.input = Input::from(value()?.as_str()),
+                "-f" | "--format" => options.format = value()?.parse()?,
+                "-v" | "--verbose" => options.verbosity += 1,
+                "-vv" => options.verbosity += 2,
+                "-j" | "--jobs" => options.jobs = parse_count(arg, value()?)?.max(1),
+                "--warmup" => options.warmup = parse_count(arg, value()?)?,
+                "--runs" => options.runs = parse_count(arg, value()?)?,
+                "--generate" => options.generate = true,
+                "--fps" => {
+                    let fps = value()?;
+                    options.fps = fps
+                        .parse()
+                        .map_err(|e| format!("Invalid value for `{arg}`: {e}"))?;
+                }
+                "--step" => options.step = true,
+                "-o" | "--output" => options.output = Some(value()?.clone()),
+                "--scale" => options.scale = parse_count(arg, value()?)?.max(1),
+                "--every" => options.every = parse_count(arg, value()?)?.max(1),
+                "--plain" => options.encoding = Encoding::Plain,
+                "--size" => options.size = Some(parse_count(arg, value()?)?),
+                "--seed" => {
+                    let seed = value()?;
+                    options.seed = Some(
+                        seed.parse()
+                            .map_err(|e| format!("Invalid value for `{arg}`: {e}"))?,
+                    );
+                }
+                "--sample" => options.sample = Some(value()?.clone()),
+                "--answer-a" => options.answer_a = Some(value()?.clone()),
+                "--answer-b" => options.answer_b = Some(value()?.clone()),
+                "-" => options.input = Input::Stdin,
+                _ if arg.starts_with('-') => return Err(format!("Unknown argument `{arg}`")),
+                _ if options.command.is_none() => options.command = Some(arg.clone()),
+                _ => options.positional.push(arg.clone()),
+            }
+        }
+        Ok(options)
+    }
+
+    /// Log the diagnostics of the solutions to stderr, as verbosely as requested.
+    fn init_logger(&self) {
+        let level = match self.verbosity {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        };
+        env_logger::Builder::new()
+            .filter_level(LevelFilter::Warn)
+            .filter_module("aoc2022", level)
+            .format_timestamp(None)
+            .init();
+    }
+
+    /// The solution named by the first positional argument.
+    fn solution(&self) -> &'static dyn Solution {
+        let name = self
+            .positional
+            .first()
+            .unwrap_or_else(|| exit_with("Missing the puzzle name"));
+        find_by_name(name).unwrap_or_else(|| exit_with("We haven't solved that yet"))
+    }
+
+    /// The day given by the first positional argument.
+    fn day(&self) -> u8 {
+        self.positional
+            .first()
+            .map(|day| parse_day(day).unwrap_or_else(|e| exit_with(&e)))
+            .unwrap_or_else(|| exit_with("Missing the day"))
+    }
+
+    /// The sample input and answers of a new day, if any.
+    fn sample(&self) -> Result<Option<Sample>> {
+        let input = match &self.sample {
+            Some(path) => fs::read_to_string(path)?,
+            None if self.answer_a.is_some() || self.answer_b.is_some() => {
+                exit_with("The answers need a `--sample` input")
+            }
+            None => return Ok(None),
+        };
+        Ok(Some(Sample {
+            input,
+            answer_a: self.answer_a.clone(),
+            answer_b: self.answer_b.clone(),
+        }))
+    }
+
+    /// The size of the inputs generated for a day, which must be valid.
+    fn size(&self, day: u8) -> usize {
+        let size = self
+            .size
+            .or_else(|| default_size(day))
+            .unwrap_or_else(|| exit_with("We cannot generate inputs for that day yet"));
+        if let Some(sizes) = valid_sizes(day).filter(|sizes| !sizes.contains(&size)) {
+            exit_with(&format!(
+                "The size must be between {} and {}",
+                sizes.start(),
+                sizes.end()
+            ));
+        }
+        size
+    }
+
+    /// The seed of the first generated input, a random one by default.
+    fn seed(&self) -> u64 {
+        self.seed
+            .unwrap_or_else(|| uuid::Uuid::new_v4().as_u64_pair().0)
+    }
+
+    fn read_input(&self, solution: &dyn Solution) -> Result<String> {
+        Ok(self.input.read(solution.day())?)
+    }
+}
+
+/// Parse a day, given as `7`, `07` or `day07`.
+fn parse_day(value: &str) -> Result<u8, String> {
+    value
+        .trim_start_matches("day")
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("Invalid day `{value}`"))
+}
+
+fn parse_count(arg: &str, value: &str) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .map_err(|e| format!("Invalid value for `{arg}`: {e}"))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}\n\n{USAGE}");
+    process::exit(2);
+}
 
 fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<()> {
     let args = std::env::args().collect::<Vec<String>>();
-    let problem = args.get(1).map(|s| s.as_str()).unwrap_or("None");
-    let result = match problem {
-        "day01a" => day01a(),
-        "day01b" => day01b(),
-        "day02a" => day02a(),
-        "day02b" => day02b(),
-        "day03a" => day03a(),
-        "day03b" => day03b(),
-        "day04a" => day04a(),
-        "day04b" => day04b(),
-        "day05a" => day05a(),
-        "day05b" => day05b(),
-        "day06a" => day06a(),
-        "day06b" => day06b(),
-        "day07a" => day07a(),
-        "day07b" => day07b(),
-        "day08a" => day08a(),
-        "day08b" => day08b(),
-        "day09a" => day09a(),
-        "day09b" => day09b(),
-        "day10a" => day10a(),
-        "day10b" => day10b(),
-        "day11a" => day11a(),
-        "day11b" => day11b(),
-        "day12a" => day12a(),
-        "day12b" => day12b(),
-        "day13a" => day13a(),
-        "day13b" => day13b(),
-        "day14a" => day14a(),
-        "day14b" => day14b(),
-        "day15a" => day15a(),
-        "day15b" => day15b(),
-        "day16a" => day16a(),
-        "day16b" => day16b(),
-        "day17a" => day17a(),
-        "day17b" => day17b(),
-        "day18a" => day18a(),
-        "day18b" => day18b(),
-        "day19a" => day19a(),
-        "day19b" => day19b(),
-        "day20a" => day20a(),
-        "day20b" => day20b(),
-        "day21a" => day21a(),
-        "day21b" => day21b(),
-        "day22a" => day22a(),
-        "day22b" => day22b(),
-        _ => "We haven't solved that yet".to_string(),
-    };
-    println!("{}", result);
+    let config = Config::load()?;
+    let options = Options::parse(args.get(1..).unwrap_or_default(), &config)
+        .unwrap_or_else(|e| exit_with(&e));
+    let command = options.command.as_deref().unwrap_or("None");
+    options.init_logger();
+    let params = config.params();
+    let solutions = WithParams::all(SOLUTIONS, &params);
+    let solutions = solutions
+        .iter()
+        .map(|s| s as &dyn Solution)
+        .collect::<Vec<_>>();
+
+    match command {
+        "list" => {
+            for solution in SOLUTIONS {
+                println!("{}", solution.name());
+            }
+        }
+        "run-all" => {
+            let (reports, elapsed) =
+                Elapsed::measure(|| run_all(&solutions, &config.input(), options.jobs));
+            match options.format {
+                Format::Text => print!("{}", render_table(&reports, elapsed)),
+                Format::Json => print!("{}", render_json(&reports, None)),
+            }
+        }
+        "verify" => {
+            let known = KnownAnswers::load(config.answers_file())?;
+            let reports = run_all(&solutions, &config.input(), options.jobs);
+            match options.format {
+                Format::Text => print!("{}", render_verification(&reports, &known)),
+                Format::Json => print!("{}", render_json(&reports, Some(&known))),
+            }
+            if reports
+                .iter()
+                .any(|r| matches!(r.verdict(&known), Verdict::Fail(_)))
+            {
+                process::exit(1);
+            }
+        }
+        "samples" => {
+            let mut samples = discover(SAMPLES_DIR)?;
+            if !options.positional.is_empty() {
+                let day = options.day();
+                samples.retain(|s| s.day == day);
+            }
+            let reports = run_samples(&samples, SOLUTIONS);
+            print!("{}", render_samples(&reports));
+            if reports
+                .iter()
+                .any(|r| matches!(r.verdict, Verdict::Fail(_)))
+            {
+                process::exit(1);
+            }
+        }
+        "pool" => {
+            let day = options.day();
+            let dir = options
+                .positional
+                .get(1)
+                .unwrap_or_else(|| exit_with("Missing the directory of the inputs"));
+            let inputs = pool::discover(dir, day)?;
+            if inputs.is_empty() {
+                exit_with(&format!(
+                    "There is no input in {}",
+                    pool::day_dir(dir, day).display()
+                ));
+            }
+            let reports = run_samples(&inputs, &solutions);
+            print!("{}", render_matrix(&reports));
+            if reports
+                .iter()
+                .any(|r| matches!(r.verdict, Verdict::Fail(_)))
+            {
+                process::exit(1);
+            }
+        }
+        "watch" => {
+            let day = options.day();
+            let input = options
+                .input
+                .path(day)
+                .unwrap_or_else(|| exit_with("We cannot watch stdin"));
+            Watcher::new(day, input, config.answers_file()).run()?;
+        }
+        "fetch" => match Fetcher::from_config(&config).fetch(options.day())? {
+            Fetched::Cached(path) => println!("{} already exists", path.display()),
+            Fetched::Downloaded(path) => println!("Downloaded {}", path.display()),
+        },
+        "generate" => {
+            let day = options.day();
+            let size = options.size(day);
+            let seed = options.seed();
+            debug!("Generating day {day} with size {size} and seed {seed}");
+            match generate(day, size, &mut Rng::new(seed)) {
+                Some(input) => print!("{input}"),
+                None => exit_with("We cannot generate inputs for that day yet"),
+            }
+        }
+        "new-day" => {
+            let sample = options.sample()?;
+            for path in Scaffold::new(".").create(options.day(), sample.as_ref())? {
+                println!("Wrote {}", path.display());
+            }
+        }
+        "crosscheck" => {
+            let solution = options.solution();
+            let implementations =
+                WithParams::all(&implementations(solution.day(), solution.part()), &params);
+            let implementations = implementations
+                .iter()
+                .map(|s| s as &dyn Solution)
+                .collect::<Vec<_>>();
+            if implementations.len() < 2 {
+                exit_with(&format!(
+                    "There is no alternative implementation of {}",
+                    solution.name()
+                ));
+            }
+            if options.generate {
+                let size = options.size(solution.day());
+                let seed = options.seed();
+                debug!(
+                    "Cross-checking {} inputs of size {size} from seed {seed}",
+                    options.runs
+                );
+                let seeds = (0..options.runs as u64).map(|i| seed.wrapping_add(i));
+                if let Some(disagreement) = search(&implementations, size, seeds) {
+                    let path = std::env::temp_dir().join(format!(
+                        "aoc2022-{}-{}.txt",
+                        solution.name(),
+                        disagreement.seed
+                    ));
+                    fs::write(&path, &disagreement.input)?;
+                    print!("{}", disagreement.crosscheck);
+                    println!(
+                        "The implementations disagree on seed {}, its input written to {}",
+                        disagreement.seed,
+                        path.display()
+                    );
+                    process::exit(1);
+                }
+                println!(
+                    "The implementations agree on {} generated inputs",
+                    options.runs
+                );
+            } else {
+                let crosscheck = Crosscheck::run(&implementations, &options.read_input(solution)?);
+                print!("{crosscheck}");
+                if !crosscheck.agrees() {
+                    println!("The implementations disagree or fail");
+                    process::exit(1);
+                }
+            }
+        }
+        "visualize" => {
+            let solution = options.solution();
+            if options.step && options.input == Input::Stdin {
+                return Err(AocError::Config(String::from(
+                    "`--step` reads the keypresses from stdin, the input cannot come from it too",
+                )));
+            }
+            let data = options.read_input(solution)?;
+            let pace = if options.step {
+                Pace::Step
+            } else {
+                Pace::Fps(options.fps)
+            };
+            let mut visualizer = AnsiVisualizer::terminal(pace);
+            match visualize(
+                solution.day(),
+                solution.part(),
+                &data,
+                &params,
+                &mut visualizer,
+            ) {
+                Some(answer) => println!("{}", answer?),
+                None => exit_with("We cannot visualize that puzzle yet"),
+            }
+        }
+        "image" => {
+            let solution = options.solution();
+            let data = options.read_input(solution)?;
+            let image = match draw(solution.day(), solution.part(), &data, &params) {
+                Some(image) => image?.scale(options.scale),
+                None => exit_with("We cannot draw that puzzle yet"),
+            };
+            let path = options
+                .output
+                .clone()
+                .unwrap_or_else(|| format!("{}.{}", solution.name(), image.extension()));
+            image.save(&path, options.encoding)?;
+            println!("Wrote {path}");
+        }
+        "frames" => {
+            let solution = options.solution();
+            let data = options.read_input(solution)?;
+            let dir = options.output.as_deref().unwrap_or("frames");
+            let mut writer = FrameWriter::new(
+                dir,
+                &solution.name(),
+                options.every,
+                options.scale,
+                options.encoding,
+            )?;
+            match visualize(solution.day(), solution.part(), &data, &params, &mut writer) {
+                Some(answer) => {
+                    answer?;
+                }
+                None => exit_with("We cannot visualize that puzzle yet"),
+            }
+            println!("Wrote {} frames into {dir}", writer.finish()?);
+        }
+        "bench" => {
+            let solution = options.solution();
+            let data = options.read_input(solution)?;
+            let solution = WithParams::new(solution, &params);
+            match bench(&solution, &data, options.warmup, options.runs)? {
+                Some(result) => print!("{result}"),
+                None => exit_with("There must be at least one run"),
+            }
+        }
+        _ => match find_by_name(command) {
+            Some(solution) => {
+                if !options.positional.is_empty() {
+                    exit_with(&format!("Unknown argument `{}`", options.positional[0]));
+                }
+                match options.format {
+                    Format::Text => {
+                        let data = options.read_input(solution)?;
+                        println!("{}", solution.solve_with(&data, &params)?);
+                    }
+                    Format::Json => {
+                        let solution = WithParams::new(solution, &params);
+                        let report = Report::run(&solution, &options.input);
+                        print!("{}", render_json(std::slice::from_ref(&report), None));
+                        if report.outcome.is_err() {
+                            process::exit(1);
+                        }
+                    }
+                }
+            }
+            None => match day_and_part(command) {
+                Some((day, part)) => return Err(AocError::Unsolved { day, part }),
+                None => exit_with(&format!("Unknown command `{command}`")),
+            },
+        },
+    }
+    Ok(())
 }
@@ -0,0 +1,116 @@
+use serde::Deserialize;
+
+use crate::{
+    day07::Disk,
+    day15::Scan,
+    day17::Tower,
+    error::Result,
+    solution::{Answer, Part, Repeated, Run, Solution},
+};
+
+/// The numbers the puzzles give in their text rather than in their input.
+///
+/// The defaults are the ones of the real puzzles. Overriding them runs the solutions on
+/// variants of the puzzles, i.e. the examples, which use smaller ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    pub day07: Disk,
+    pub day15: Scan,
+    pub day17: Tower,
+}
+
+/// A solution run with other parameters than the ones of the puzzle text.
+#[derive(Clone, Copy)]
+pub struct WithParams<'a> {
+    solution: &'a dyn Solution,
+    params: &'a Params,
+}
+
+impl<'a> WithParams<'a> {
+    pub fn new(solution: &'a dyn Solution, params: &'a Params) -> Self {
+        WithParams { solution, params }
+    }
+
+    /// Give the same parameters to each solution.
+    pub fn all(solutions: &[&'a dyn Solution], params: &'a Params) -> Vec<Self> {
+        solutions
+            .iter()
+            .map(|solution| WithParams::new(*solution, params))
+            .collect()
+    }
+}
+
+impl Solution for WithParams<'_> {
+    fn day(&self) -> u8 {
+        self.solution.day()
+    }
+
+    fn part(&self) -> Part {
+        self.solution.part()
+    }
+
+    fn run(&self, input: &str) -> Result<Run> {
+        self.solution.run_with(input, self.params)
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer> {
+        self.solution.solve_with(input, self.params)
+    }
+
+    fn run_with(&self, input: &str, params: &Params) -> Result<Run> {
+        self.solution.run_with(input, params)
+    }
+
+    fn solve_with(&self, input: &str, params: &Params) -> Result<Answer> {
+        self.solution.solve_with(input, params)
+    }
+
+    fn repeat(&self, input: &str, warmup: usize, runs: usize) -> Result<Repeated> {
+        self.solution.repeat_with(input, self.params, warmup, runs)
+    }
+
+    fn repeat_with(
+        &self,
+        input: &str,
+        params: &Params,
+        warmup: usize,
+        runs: usize,
+    ) -> Result<Repeated> {
+        self.solution.repeat_with(input, params, warmup, runs)
+    }
+
+    fn variant(&self) -> Option<&'static str> {
+        self.solution.variant()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::find_by_name;
+
+    #[test]
+    fn test_with_params() {
+        let params = Params {
+            day17: Tower { rocks: 10 },
+            ..Params::default()
+        };
+        let day17a = find_by_name("day17a").unwrap();
+        let input = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        assert_eq!(day17a.solve(input).unwrap(), Answer::Int(3068));
+
+        let tuned = WithParams::new(day17a, &params);
+        assert_eq!(tuned.name(), "day17a");
+        assert_eq!(tuned.solve(input).unwrap(), Answer::Int(17));
+        assert_eq!(tuned.run(input).unwrap().answer, Answer::Int(17));
+
+        // The puzzles without parameters ignore them.
+        let day20a = find_by_name("day20a").unwrap();
+        let tuned = WithParams::new(day20a, &params);
+        assert_eq!(
+            tuned.solve("1\n2\n-3\n3\n-2\n0\n4\n").unwrap(),
+            Answer::Int(3)
+        );
+    }
+}
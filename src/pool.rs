@@ -0,0 +1,162 @@
+use std::{
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answers::{KnownAnswers, Verdict},
+    error::Result,
+    samples::{Sample, SampleReport, ANSWERS_EXTENSION},
+};
+
+/// The directory holding the inputs of a day in a pool, i.e. `pool/day15`.
+pub fn day_dir<P: AsRef<Path>>(dir: P, day: u8) -> PathBuf {
+    dir.as_ref().join(format!("day{day:02}"))
+}
+
+/// Find the inputs of a day pooled from several accounts, stored as
+/// `<dir>/dayNN/<name>.txt`, ordered by name.
+///
+/// Their expected answers are stored next to them in `<name>.answers`, in the format of
+/// the known answers. A missing directory means no inputs.
+pub fn discover<P: AsRef<Path>>(dir: P, day: u8) -> Result<Vec<Sample>> {
+    let entries = match fs::read_dir(day_dir(dir, day)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|e| e != "txt") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        inputs.push(Sample {
+            name: name.to_string(),
+            day,
+            answers: KnownAnswers::load(path.with_extension(ANSWERS_EXTENSION))?,
+            params: None,
+            path,
+        });
+    }
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(inputs)
+}
+
+/// Render the verdicts as a matrix, one row per input and one column per puzzle part,
+/// followed by the answer and the reason of each failure.
+pub fn render_matrix(reports: &[SampleReport]) -> String {
+    let mut inputs = Vec::<&str>::new();
+    let mut puzzles = Vec::<&str>::new();
+    for r in reports {
+        if !inputs.contains(&r.sample.as_str()) {
+            inputs.push(&r.sample);
+        }
+        if !puzzles.contains(&r.report.name.as_str()) {
+            puzzles.push(&r.report.name);
+        }
+    }
+    let width = inputs
+        .iter()
+        .map(|i| i.chars().count())
+        .chain(std::iter::once("Input".len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut matrix = format!("{:<width$}", "Input");
+    for puzzle in &puzzles {
+        write!(matrix, " {puzzle:<8}").unwrap();
+    }
+    for input in &inputs {
+        write!(matrix, "\n{input:<width$}").unwrap();
+        for puzzle in &puzzles {
+            let verdict = reports
+                .iter()
+                .find(|r| r.sample == *input && r.report.name == *puzzle)
+                .map_or(String::from("-"), |r| r.verdict.to_string());
+            write!(matrix, " {verdict:<8}").unwrap();
+        }
+    }
+    matrix = matrix
+        .lines()
+        .map(|l| l.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n";
+
+    for r in reports {
+        if let Verdict::Fail(reason) = &r.verdict {
+            match &r.report.outcome {
+                Ok(run) => writeln!(
+                    matrix,
+                    "{} {}: {} ({reason})",
+                    r.sample,
+                    r.report.name,
+                    run.answer.inline()
+                ),
+                Err(e) => writeln!(matrix, "{} {}: {e}", r.sample, r.report.name),
+            }
+            .unwrap();
+        }
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{samples::run_samples, solution::SOLUTIONS};
+
+    #[test]
+    fn test_pool() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-pool-{}", std::process::id()));
+        let day_dir = day_dir(&dir, 6);
+        fs::create_dir_all(&day_dir).unwrap();
+        for (name, input, answers) in [
+            (
+                "carol",
+                "mjqjpqmgbljsphdztnvjfqwrcgmsb",
+                Some("day06a = 7\nday06b = 19"),
+            ),
+            (
+                "alice",
+                "bvwbjplbgvbhsrlpgdmjqwftvncz",
+                Some("day06a = 5\nday06b = 24"),
+            ),
+            ("bob", "nppdvjthqldpwncqszvftbrmjlhg", None),
+            ("dave", "aaaa", Some("day06a = 4")),
+        ] {
+            fs::write(day_dir.join(format!("{name}.txt")), input).unwrap();
+            if let Some(answers) = answers {
+                fs::write(day_dir.join(format!("{name}.answers")), answers).unwrap();
+            }
+        }
+
+        let inputs = discover(&dir, 6).unwrap();
+        assert_eq!(
+            inputs.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(),
+            ["alice", "bob", "carol", "dave"]
+        );
+        assert!(discover(&dir, 7).unwrap().is_empty());
+
+        let reports = run_samples(&inputs, SOLUTIONS);
+        fs::remove_dir_all(dir).unwrap();
+        assert_eq!(
+            render_matrix(&reports),
+            "Input day06a   day06b
+alice PASS     FAIL
+bob   UNKNOWN  UNKNOWN
+carol PASS     PASS
+dave  FAIL     FAIL
+alice day06b: 23 (expected = 24)
+dave day06a: Invalid puzzle state: No valid marker found
+dave day06b: Invalid puzzle state: No valid marker found
+"
+        );
+    }
+}
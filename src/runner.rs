@@ -0,0 +1,430 @@
+use std::{
+    fmt::Write,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::{KnownAnswers, Verdict},
+    input::Input,
+    solution::{Answer, Part, Run, Solution},
+};
+
+/// How the reports are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// Tables meant for humans.
+    #[default]
+    Text,
+    /// One JSON object per line and per report, see [`render_json`].
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format `{s}`, expected `text` or `json`")),
+        }
+    }
+}
+
+/// The result of running one solution against its input.
+#[derive(Debug)]
+pub struct Report {
+    pub name: String,
+    pub day: u8,
+    pub part: Part,
+    /// The file the input was read from, `None` for stdin.
+    pub input: Option<PathBuf>,
+    pub outcome: Result<Run, String>,
+}
+
+impl Report {
+    /// Run a solution against its input.
+    pub fn run(solution: &dyn Solution, input: &Input) -> Self {
+        Report {
+            name: solution.name(),
+            day: solution.day(),
+            part: solution.part(),
+            input: input.path(solution.day()),
+            outcome: run_one(solution, input),
+        }
+    }
+
+    /// Check the answer against the known answers, a failed run always failing.
+    pub fn verdict(&self, known: &KnownAnswers) -> Verdict {
+        match &self.outcome {
+            Ok(run) => known.check(self.day, self.part, &run.answer),
+            Err(e) => Verdict::Fail(e.clone()),
+        }
+    }
+}
+
+/// The number of threads [`run_all`] uses by default, one per available core.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Run each solution against its input, the file of its day when `input` is a directory,
+/// on a pool of `jobs` threads.
+///
+/// The threads take the next solution to run as soon as they are done with one, so that
+/// the slow ones do not hold up the others. The reports still come in the order of the
+/// solutions. A solution panicking is reported as a failure instead of aborting the
+/// other runs.
+pub fn run_all(solutions: &[&dyn Solution], input: &Input, jobs: usize) -> Vec<Report> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, solutions.len().max(1)) {
+            let (next, sender) = (&next, sender.clone());
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(solution) = solutions.get(index) else {
+                    break;
+                };
+                let report = Report::run(*solution, input);
+                sender.send((index, report)).unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut reports = receiver.into_iter().collect::<Vec<_>>();
+    reports.sort_by_key(|(index, _)| *index);
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+pub fn run_one(solution: &dyn Solution, input: &Input) -> Result<Run, String> {
+    let data = input
+        .read(solution.day())
+        .map_err(|e| format!("Could not load input: {e}"))?;
+    run_data(solution, &data)
+}
+
+/// The time taken by a batch of solutions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elapsed {
+    pub wall: Duration,
+    /// User and system CPU time of the whole process, all threads included. `None` where
+    /// it cannot be measured.
+    pub cpu: Option<Duration>,
+}
+
+impl Elapsed {
+    /// Run `f`, measuring the time it takes.
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Elapsed) {
+        let (start, cpu_start) = (Instant::now(), cpu_time());
+        let result = f();
+        let elapsed = Elapsed {
+            wall: start.elapsed(),
+            cpu: cpu_time()
+                .zip(cpu_start)
+                .map(|(end, start)| end.saturating_sub(start)),
+        };
+        (result, elapsed)
+    }
+}
+
+/// The CPU time used by the process so far, in user and system mode.
+#[cfg(unix)]
+pub fn cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: getrusage only writes into the struct it is given, which it fills on
+    // success.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    let duration = |t: libc::timeval| {
+        Duration::from_secs(t.tv_sec as u64) + Duration::from_micros(t.tv_usec as u64)
+    };
+    Some(duration(usage.ru_utime) + duration(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+pub fn cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Run a solution against some input data, catching its panics.
+pub fn run_data(solution: &dyn Solution, data: &str) -> Result<Run, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| solution.run(data)))
+        .map_err(|_| String::from("Panicked"))?
+        .map_err(|e| e.to_string())
+}
+
+/// Render the reports as a table with the timings of each stage and their sums, then the
+/// CPU time and the wall time it took to run them all.
+///
+/// The sums add up the wall time of each solution. The solutions running in parallel,
+/// their times overlap and the sums may exceed the overall wall time.
+pub fn render_table(reports: &[Report], elapsed: Elapsed) -> String {
+    let width = reports
+        .iter()
+        .filter_map(|r| r.outcome.as_ref().ok())
+        .map(|run| summary(&run.answer).chars().count())
+        .chain(std::iter::once("Answer".len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut table = String::new();
+    let mut parse_total = Duration::ZERO;
+    let mut solve_total = Duration::ZERO;
+    writeln!(
+        table,
+        "{:<12} {:<width$} {:>10} {:>10}",
+        "Puzzle", "Answer", "Parse", "Solve"
+    )
+    .unwrap();
+    for report in reports {
+        match &report.outcome {
+            Ok(run) => {
+                parse_total += run.parse;
+                solve_total += run.solve;
+                writeln!(
+                    table,
+                    "{:<12} {:<width$} {:>10} {:>10}",
+                    report.name,
+                    summary(&run.answer),
+                    format_duration(run.parse),
+                    format_duration(run.solve)
+                )
+                .unwrap();
+            }
+            Err(e) => writeln!(table, "{:<12} {e}", report.name).unwrap(),
+        }
+    }
+    writeln!(
+        table,
+        "{:<12} {:<width$} {:>10} {:>10}",
+        "Sum of times",
+        format_duration(parse_total + solve_total),
+        format_duration(parse_total),
+        format_duration(solve_total)
+    )
+    .unwrap();
+    let cpu = elapsed
+        .cpu
+        .map_or_else(|| String::from("unavailable"), format_duration);
+    writeln!(table, "{:<12} {cpu}", "CPU").unwrap();
+    writeln!(table, "{:<12} {}", "Wall", format_duration(elapsed.wall)).unwrap();
+    table
+}
+
+/// Render the verdict of each report, with the answer and the reason of any failure.
+pub fn render_verification(reports: &[Report], known: &KnownAnswers) -> String {
+    let mut table = String::new();
+    for report in reports {
+        let verdict = report.verdict(known);
+        write!(table, "{:<8} {:<7}", report.name, verdict.to_string()).unwrap();
+        match (&report.outcome, verdict) {
+            (Ok(run), Verdict::Fail(reason)) => {
+                writeln!(table, " {} ({reason})", run.answer.inline())
+            }
+            (Ok(run), _) => writeln!(table, " {}", run.answer.inline()),
+            (Err(e), _) => writeln!(table, " {e}"),
+        }
+        .unwrap();
+    }
+    table
+}
+
+/// A report as serialized by [`render_json`].
+#[derive(Serialize)]
+struct Record<'a> {
+    name: &'a str,
+    day: u8,
+    part: Part,
+    answer: Option<&'a Answer>,
+    timings: Option<Timings>,
+    input: Option<&'a Path>,
+    error: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verdict: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct Timings {
+    parse_ns: u128,
+    solve_ns: u128,
+    total_ns: u128,
+}
+
+/// Render each report as a JSON object on its own line, with its verdict when the known
+/// answers are given.
+///
+/// ```text
+/// {"name":"day01a","day":1,"part":"a","answer":24000,"timings":{"parse_ns":2000000,
+///  "solve_ns":3000000,"total_ns":5000000},"input":"assets/day01.txt","error":null}
+/// ```
+pub fn render_json(reports: &[Report], known: Option<&KnownAnswers>) -> String {
+    let mut lines = String::new();
+    for report in reports {
+        let verdict = known.map(|known| report.verdict(known));
+        let run = report.outcome.as_ref().ok();
+        let record = Record {
+            name: &report.name,
+            day: report.day,
+            part: report.part,
+            answer: run.map(|run| &run.answer),
+            timings: run.map(|run| Timings {
+                parse_ns: run.parse.as_nanos(),
+                solve_ns: run.solve.as_nanos(),
+                total_ns: run.total().as_nanos(),
+            }),
+            input: report.input.as_deref(),
+            error: report.outcome.as_ref().err().map(String::as_str),
+            verdict: verdict.as_ref().map(Verdict::to_string),
+            reason: match &verdict {
+                Some(Verdict::Fail(reason)) if run.is_some() => Some(reason),
+                _ => None,
+            },
+        };
+        // Serializing plain structs into a string cannot fail.
+        writeln!(lines, "{}", serde_json::to_string(&record).unwrap()).unwrap();
+    }
+    lines
+}
+
+/// An answer short enough for a table cell, pictures only showing their size.
+fn summary(answer: &Answer) -> String {
+    match answer {
+        Answer::Picture(rows) => format!(
+            "[{}x{} picture]",
+            rows.lines()
+                .map(|row| row.chars().count())
+                .max()
+                .unwrap_or_default(),
+            rows.lines().count()
+        ),
+        _ => answer.inline(),
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{duration:.2?}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::SOLUTIONS;
+
+    fn reports() -> Vec<Report> {
+        vec![
+            Report {
+                name: String::from("day01a"),
+                day: 1,
+                part: Part::A,
+                input: Some(PathBuf::from("assets/day01.txt")),
+                outcome: Ok(Run {
+                    answer: Answer::Int(24000),
+                    parse: Duration::from_millis(2),
+                    solve: Duration::from_millis(3),
+                }),
+            },
+            Report {
+                name: String::from("day06a"),
+                day: 6,
+                part: Part::A,
+                input: None,
+                outcome: Err(String::from("Could not load input")),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_table() {
+        let reports = reports();
+        assert_eq!(
+            render_table(
+                &reports,
+                Elapsed {
+                    wall: Duration::from_millis(4),
+                    cpu: Some(Duration::from_millis(7)),
+                }
+            ),
+            "Puzzle       Answer      Parse      Solve
+day01a       24000      2.00ms     3.00ms
+day06a       Could not load input
+Sum of times 5.00ms     2.00ms     3.00ms
+CPU          7.00ms
+Wall         4.00ms
+"
+        );
+        assert!(render_table(
+            &reports,
+            Elapsed {
+                wall: Duration::from_millis(4),
+                cpu: None,
+            }
+        )
+        .ends_with("CPU          unavailable\nWall         4.00ms\n"));
+
+        let known = "day01a = 24001\nday06a = 7"
+            .parse::<KnownAnswers>()
+            .unwrap();
+        assert_eq!(
+            render_verification(&reports, &known),
+            "day01a   FAIL    24000 (expected = 24001)
+day06a   FAIL    Could not load input
+"
+        );
+    }
+
+    #[test]
+    fn test_elapsed() {
+        let (sum, elapsed) = Elapsed::measure(|| (0..2_000_000_u64).map(|n| n % 7).sum::<u64>());
+        assert!(sum > 0);
+        if cfg!(unix) {
+            assert!(elapsed.cpu.is_some());
+        }
+    }
+
+    #[test]
+    fn test_run_all() {
+        let solutions = &SOLUTIONS[..6];
+        let names = run_all(solutions, &Input::Default, 4)
+            .into_iter()
+            .map(|r| {
+                assert!(r.outcome.is_ok(), "{} failed", r.name);
+                r.name
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            solutions.iter().map(|s| s.name()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let known = "day01a = 24001".parse::<KnownAnswers>().unwrap();
+        assert_eq!(
+            render_json(&reports(), Some(&known)),
+            r#"{"name":"day01a","day":1,"part":"a","answer":24000,"timings":{"parse_ns":2000000,"solve_ns":3000000,"total_ns":5000000},"input":"assets/day01.txt","error":null,"verdict":"FAIL","reason":"expected = 24001"}
+{"name":"day06a","day":6,"part":"a","answer":null,"timings":null,"input":null,"error":"Could not load input","verdict":"FAIL"}
+"#
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use std::{
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answers::{KnownAnswers, Verdict},
+    error::{AocError, Result},
+    input::Input,
+    params::{Params, WithParams},
+    runner::Report,
+    solution::Solution,
+};
+
+/// Directory holding the puzzle examples, relative to the crate root.
+pub const SAMPLES_DIR: &str = "samples";
+
+/// Extension of the files storing the expected answers of an example, next to it.
+pub const ANSWERS_EXTENSION: &str = "answers";
+
+/// Extension of the files storing the numbers of the puzzle text an example overrides,
+/// next to it.
+pub const PARAMS_EXTENSION: &str = "toml";
+
+/// An example of a puzzle, stored as `dayNN.txt`, or `dayNN-k.txt` when a day has
+/// several.
+///
+/// Its expected answers are stored in `dayNN[-k].answers`, in the format of the known
+/// answers, and the numbers of the puzzle text it overrides in `dayNN[-k].toml`, in the
+/// format of the day tables of the configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    /// The file name without its extension, i.e. `day06-2`.
+    pub name: String,
+    pub day: u8,
+    pub path: PathBuf,
+    pub answers: KnownAnswers,
+    /// `None` to run the solutions as they are given.
+    pub params: Option<Params>,
+}
+
+impl Sample {
+    /// Parse the day out of a file name like `day06-2.txt`.
+    ///
+    /// ```
+    /// use aoc2022::samples::Sample;
+    ///
+    /// assert_eq!(Sample::parse_file_name("day06.txt"), Some(("day06", 6)));
+    /// assert_eq!(Sample::parse_file_name("day06-2.txt"), Some(("day06-2", 6)));
+    /// assert_eq!(Sample::parse_file_name("day06.answers"), None);
+    /// assert_eq!(Sample::parse_file_name("day6-a.txt"), None);
+    /// ```
+    pub fn parse_file_name(file_name: &str) -> Option<(&str, u8)> {
+        let name = file_name.strip_suffix(".txt")?;
+        let (day, index) = match name.split_once('-') {
+            Some((day, index)) => (day, Some(index)),
+            None => (name, None),
+        };
+        let day = day.strip_prefix("day").filter(|day| day.len() == 2)?;
+        let is_number = |k: &str| !k.is_empty() && k.chars().all(|c| c.is_ascii_digit());
+        if index.is_some_and(|k| !is_number(k)) {
+            return None;
+        }
+        Some((name, day.parse().ok()?))
+    }
+}
+
+/// Read the numbers of the puzzle text an example overrides, `None` when it has no such
+/// file.
+pub fn load_params<P: AsRef<Path>>(path: P) -> Result<Option<Params>> {
+    let path = path.as_ref();
+    match fs::read_to_string(path) {
+        Ok(data) => toml::from_str(&data)
+            .map(Some)
+            .map_err(|e| AocError::Config(format!("{}: {e}", path.display()))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Find the examples in a directory, ordered by day, a missing directory meaning no
+/// examples.
+pub fn discover<P: AsRef<Path>>(dir: P) -> Result<Vec<Sample>> {
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut samples = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some((name, day)) = path
+            .file_name()
+            .and_then(|f| f.to_str())
+            .and_then(Sample::parse_file_name)
+        else {
+            continue;
+        };
+        samples.push(Sample {
+            name: name.to_string(),
+            day,
+            answers: KnownAnswers::load(path.with_extension(ANSWERS_EXTENSION))?,
+            params: load_params(path.with_extension(PARAMS_EXTENSION))?,
+            path,
+        });
+    }
+    // `day06` comes before `day06-2`, which comes before `day06-10`.
+    samples.sort_by_key(|s| {
+        let index = s
+            .name
+            .split_once('-')
+            .and_then(|(_, k)| k.parse::<u32>().ok());
+        (s.day, index)
+    });
+    Ok(samples)
+}
+
+/// The result of running a solution against an example.
+#[derive(Debug)]
+pub struct SampleReport {
+    pub sample: String,
+    pub report: Report,
+    /// An unknown verdict means the expected answer is still pending.
+    pub verdict: Verdict,
+}
+
+/// Run every solution of the day of each example against it.
+///
+/// An expected answer no solution gives is reported as a failure too, so that no example
+/// is silently skipped.
+pub fn run_samples(samples: &[Sample], solutions: &[&dyn Solution]) -> Vec<SampleReport> {
+    let mut reports = Vec::new();
+    for sample in samples {
+        let input = Input::Path(sample.path.clone());
+        for solution in solutions.iter().filter(|s| s.day() == sample.day) {
+            let report = match &sample.params {
+                Some(params) => Report::run(&WithParams::new(*solution, params), &input),
+                None => Report::run(*solution, &input),
+            };
+            reports.push(SampleReport {
+                sample: sample.name.clone(),
+                verdict: report.verdict(&sample.answers),
+                report,
+            });
+        }
+        for (day, part) in sample.answers.puzzles() {
+            if solutions.iter().any(|s| s.day() == day && s.part() == part) {
+                continue;
+            }
+            let report = Report {
+                name: format!("day{day:02}{part}"),
+                day,
+                part,
+                input: Some(sample.path.clone()),
+                outcome: Err(AocError::Unsolved { day, part }.to_string()),
+            };
+            reports.push(SampleReport {
+                sample: sample.name.clone(),
+                verdict: report.verdict(&sample.answers),
+                report,
+            });
+        }
+    }
+    reports
+}
+
+/// Render the verdict of each example, with the answer and the reason of any failure.
+pub fn render_samples(reports: &[SampleReport]) -> String {
+    let mut table = String::new();
+    for SampleReport {
+        sample,
+        report,
+        verdict,
+    } in reports
+    {
+        let verdict_label = match verdict {
+            Verdict::Unknown => String::from("PENDING"),
+            verdict => verdict.to_string(),
+        };
+        write!(table, "{sample:<10} {:<8} {verdict_label:<7}", report.name).unwrap();
+        match (&report.outcome, verdict) {
+            (Ok(run), Verdict::Fail(reason)) => {
+                writeln!(table, " {} ({reason})", run.answer.inline())
+            }
+            (Ok(run), _) => writeln!(table, " {}", run.answer.inline()),
+            (Err(e), _) => writeln!(table, " {e}"),
+        }
+        .unwrap();
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::SOLUTIONS;
+
+    /// The example answers our solutions do not find yet, as `<sample> <puzzle>`. They must
+    /// still fail, so that they are taken off the list once fixed.
+    const UNFINISHED: &[&str] = &[
+        "day09 day09b",
+        "day09-2 day09b",
+        "day11 day11b",
+        "day12 day12b",
+        "day15 day15b",
+        "day16 day16a",
+        "day16 day16b",
+        "day17 day17b",
+        "day18 day18b",
+        "day19 day19a",
+        "day19 day19b",
+        "day21 day21b",
+        "day22 day22b",
+    ];
+
+    /// Every example must have an expected answer, and give it unless it is unfinished.
+    #[test]
+    fn test_samples() {
+        let samples = discover(SAMPLES_DIR).unwrap();
+        assert!(!samples.is_empty());
+        let reports = run_samples(&samples, SOLUTIONS);
+        let label = |r: &SampleReport| format!("{} {}", r.sample, r.report.name);
+
+        let pending = reports
+            .iter()
+            .filter(|r| r.verdict == Verdict::Unknown)
+            .map(label)
+            .collect::<Vec<_>>();
+        assert!(pending.is_empty(), "Pending sample answers: {pending:?}");
+
+        let (unfinished, failed): (Vec<_>, Vec<_>) = reports
+            .into_iter()
+            .filter(|r| matches!(r.verdict, Verdict::Fail(_)))
+            .partition(|r| UNFINISHED.contains(&label(r).as_str()));
+        assert!(failed.is_empty(), "\n{}", render_samples(&failed));
+        assert_eq!(
+            unfinished.iter().map(label).collect::<Vec<_>>(),
+            UNFINISHED,
+            "Some unfinished sample answers are found now"
+        );
+    }
+}
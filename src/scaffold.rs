@@ -0,0 +1,256 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{AocError, Result},
+    input::{input_path, ASSETS_DIR},
+};
+
+/// Source of a new day, `xx` standing for the day number.
+pub const TEMPLATE: &str = include_str!("dayxx.rs");
+
+/// Longest line rustfmt lets through, for the lists we rewrite.
+const MAX_WIDTH: usize = 100;
+
+/// The sample input of a puzzle, with the expected answers if known.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sample {
+    pub input: String,
+    pub answer_a: Option<String>,
+    pub answer_b: Option<String>,
+}
+
+/// Render the source of a day from the template, with its tests pre-filled from the sample.
+pub fn render(day: u8, sample: Option<&Sample>) -> String {
+    let mut source = TEMPLATE
+        .replace(", xx,", &format!(", {day},"))
+        .replace("xx", &format!("{day:02}"));
+    if let Some(sample) = sample {
+        let escaped = sample.input.replace('\\', "\\\\").replace('"', "\\\"");
+        source = source.replace(
+            "const RAW_INPUT: &str = \"\n\";",
+            &format!("const RAW_INPUT: &str = \"{escaped}\";"),
+        );
+        for (part, answer) in [('a', &sample.answer_a), ('b', &sample.answer_b)] {
+            if let Some(answer) = answer {
+                source = source.replace(
+                    &format!("fn test_day{day:02}{part}_sample() {{}}"),
+                    &format!(
+                        "fn test_day{day:02}{part}_sample() {{
+        let data = parse_input_a(RAW_INPUT).unwrap();
+        let answer = process_input_{part}(&data).unwrap();
+        assert_eq!(answer.to_string(), \"{}\");
+    }}",
+                        answer.replace('\\', "\\\\").replace('"', "\\\"")
+                    ),
+                );
+            }
+        }
+    }
+    source
+}
+
+/// Declare the module of a day in lib.rs, keeping the days sorted.
+///
+/// ```
+/// use aoc2022::scaffold::register_module;
+///
+/// let lib = "pub mod day01;\npub mod day03;\npub mod error;\n";
+/// assert_eq!(
+///     register_module(lib, 2).unwrap(),
+///     "pub mod day01;\npub mod day02;\npub mod day03;\npub mod error;\n"
+/// );
+/// ```
+pub fn register_module(lib: &str, day: u8) -> Result<String> {
+    let declaration = format!("pub mod day{day:02};");
+    let mut lines = lib.lines().collect::<Vec<_>>();
+    if lines.contains(&declaration.as_str()) {
+        return Err(AocError::invalid_state(format!(
+            "day{day:02} is already declared"
+        )));
+    }
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("pub mod day"))
+        .map(|(n, l)| (n, *l))
+        .collect::<Vec<_>>();
+    let n = match days.iter().find(|(_, l)| *l > declaration.as_str()) {
+        Some((n, _)) => *n,
+        None => days
+            .last()
+            .map(|(n, _)| n + 1)
+            .ok_or_else(|| AocError::invalid_state("There are no day modules"))?,
+    };
+    lines.insert(n, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Import the module of a day in solution.rs and add both its parts to the registry.
+pub fn register_solutions(source: &str, day: u8) -> Result<String> {
+    let module = format!("day{day:02}");
+    let missing = || AocError::invalid_state("Cannot find the solution registry");
+
+    // The imports, as laid out by rustfmt.
+    let start = source.find("use crate::{\n").ok_or_else(missing)?;
+    let end = start + source[start..].find("};\n").ok_or_else(missing)?;
+    let mut items = source[start + "use crate::{\n".len()..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<_>>();
+    if items.contains(&module.as_str()) {
+        return Err(AocError::invalid_state(format!(
+            "{module} is already registered"
+        )));
+    }
+    let n = items
+        .iter()
+        .position(|item| !item.starts_with("day") || *item > module.as_str())
+        .unwrap_or(items.len());
+    items.insert(n, &module);
+    let imports = format!("use crate::{{\n{}", wrap_items(&items));
+
+    // The registry, one part per line.
+    let registry = source.find("pub static SOLUTIONS").ok_or_else(missing)?;
+    let registry_end = registry + source[registry..].find("];").ok_or_else(missing)?;
+    let mut entries = source[registry..registry_end]
+        .lines()
+        .skip(1)
+        .collect::<Vec<_>>();
+    let part_a = format!("    &{module}::Day{day:02}a,");
+    let part_b = format!("    &{module}::Day{day:02}b,");
+    let n = entries
+        .iter()
+        .position(|e| *e > part_a.as_str())
+        .unwrap_or(entries.len());
+    entries.insert(n, &part_b);
+    entries.insert(n, &part_a);
+    let header = source[registry..].lines().next().ok_or_else(missing)?;
+
+    Ok(format!(
+        "{}{imports}{}{header}\n{}\n{}",
+        &source[..start],
+        &source[end..registry],
+        entries.join("\n"),
+        &source[registry_end..]
+    ))
+}
+
+/// Lay out the items of a list over as few lines as possible.
+fn wrap_items(items: &[&str]) -> String {
+    let mut lines = Vec::new();
+    let mut line = String::from("   ");
+    for item in items {
+        if line.len() + item.len() + 2 > MAX_WIDTH {
+            lines.push(line);
+            line = String::from("   ");
+        }
+        line.push(' ');
+        line.push_str(item);
+        line.push(',');
+    }
+    lines.push(line);
+    lines.join("\n") + "\n"
+}
+
+/// Creates the files of a new day in a crate.
+#[derive(Clone, Debug)]
+pub struct Scaffold {
+    root: PathBuf,
+}
+
+impl Scaffold {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Scaffold {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Create the module and the empty input of a day and register its solutions,
+    /// returning the paths of the files created or updated.
+    ///
+    /// Nothing is written when the module already exists.
+    pub fn create(&self, day: u8, sample: Option<&Sample>) -> Result<Vec<PathBuf>> {
+        let src = self.root.join("src");
+        let module = src.join(format!("day{day:02}.rs"));
+        if module.exists() {
+            return Err(AocError::invalid_state(format!(
+                "{} already exists",
+                module.display()
+            )));
+        }
+        let lib = src.join("lib.rs");
+        let solution = src.join("solution.rs");
+        let lib_source = register_module(&fs::read_to_string(&lib)?, day)?;
+        let solution_source = register_solutions(&fs::read_to_string(&solution)?, day)?;
+
+        fs::write(&module, render(day, sample))?;
+        fs::write(&lib, lib_source)?;
+        fs::write(&solution, solution_source)?;
+        let mut paths = vec![module, lib, solution];
+
+        let asset = input_path(&self.root.join(ASSETS_DIR), day);
+        if !asset.exists() {
+            fs::create_dir_all(self.root.join(ASSETS_DIR))?;
+            fs::write(&asset, "")?;
+            paths.push(asset);
+        }
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let source = render(7, None);
+        assert!(source.contains("solution!(Day07a, 7, A, parse_input_a"));
+        assert!(source.contains("fn test_day07b_sample() {}"));
+        assert!(!source.contains("xx"));
+
+        let sample = Sample {
+            input: String::from("a \"b\"\n"),
+            answer_a: Some(String::from("1")),
+            answer_b: None,
+        };
+        let source = render(7, Some(&sample));
+        assert!(source.contains("const RAW_INPUT: &str = \"a \\\"b\\\"\n\";"));
+        assert!(source.contains("assert_eq!(answer.to_string(), \"1\");"));
+        assert!(source.contains("fn test_day07b_sample() {}"));
+    }
+
+    #[test]
+    fn test_register_solutions() {
+        let source = "use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day20, error::Result, nomstr,
+};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day14::Day14a,
+    &day20::Day20a,
+];
+";
+        assert_eq!(
+            register_solutions(source, 15).unwrap(),
+            "use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day20, error::Result, nomstr,
+};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day14::Day14a,
+    &day15::Day15a,
+    &day15::Day15b,
+    &day20::Day20a,
+];
+"
+        );
+        assert!(register_solutions(source, 14).is_err());
+    }
+}
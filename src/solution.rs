@@ -0,0 +1,655 @@
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self},
+    combinator::{all_consuming, value},
+    sequence::{pair, preceded},
+    Finish, IResult,
+};
+use serde::{Serialize, Serializer};
+
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day17, day18, day20, day21, day22, error::Result, nomstr, params::Params,
+};
+
+/// The value produced by a solution.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    /// A picture drawn by the puzzle, its rows separated by newlines.
+    Picture(String),
+}
+
+impl Answer {
+    /// Build a picture from its rows, trailing newlines being ignored.
+    pub fn picture<S: AsRef<str>>(rows: S) -> Self {
+        Answer::Picture(rows.as_ref().trim_end_matches('\n').to_string())
+    }
+
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Parse the single line form of an answer: an integer, a picture if it has several
+    /// rows, or else some text.
+    pub fn from_inline(s: &str) -> Self {
+        match s.parse::<i128>() {
+            Ok(n) => Answer::Int(n),
+            Err(_) if s.contains('/') => Answer::Picture(s.replace('/', "\n")),
+            Err(_) => Answer::Text(s.to_string()),
+        }
+    }
+
+    /// The answer on a single line, the rows of a picture being separated by `/`.
+    ///
+    /// It parses back into the same answer:
+    ///
+    /// ```
+    /// use aoc2022::solution::Answer;
+    ///
+    /// let picture = Answer::picture("#.\n.#\n");
+    /// assert_eq!(picture.inline(), "#./.#");
+    /// assert_eq!(Answer::from_inline(&picture.inline()), picture);
+    /// assert_eq!(Answer::from_inline("42"), Answer::Int(42));
+    /// assert_eq!(Answer::from_inline("CMZ"), Answer::Text(String::from("CMZ")));
+    /// ```
+    pub fn inline(&self) -> String {
+        match self {
+            Answer::Int(n) => n.to_string(),
+            Answer::Text(text) => text.clone(),
+            Answer::Picture(rows) => rows.replace('\n', "/"),
+        }
+    }
+}
+
+/// Pictures are displayed over several lines, see [`Answer::inline`] for a single line.
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(text) | Answer::Picture(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Integers are serialized as numbers, texts and pictures as strings.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Int(n) => serializer.serialize_i128(*n),
+            Answer::Text(text) | Answer::Picture(text) => serializer.serialize_str(text),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Answer::from_inline(s))
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(item: $t) -> Self {
+                    Answer::Int(item as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<i128> for Answer {
+    fn from(item: i128) -> Self {
+        Answer::Int(item)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(item: String) -> Self {
+        Answer::Text(item)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(item: &str) -> Self {
+        Answer::Text(item.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub fn parse(i: &str) -> IResult<&str, Part> {
+        alt((value(Part::A, tag("a")), value(Part::B, tag("b"))))(i)
+    }
+}
+
+nomstr!(Part);
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+/// The outcome of a solution run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Run {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// The outcome of a solution processing the same parsed input repeatedly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repeated {
+    /// The answer of the last timed run, `None` when there was none.
+    pub answer: Option<Answer>,
+    pub parse: Duration,
+    /// The time taken by each timed run of the process stage.
+    pub solve: Vec<Duration>,
+}
+
+/// A puzzle solution for a specific day and part.
+///
+/// The day modules implement it with the [`solution!`](crate::solution!) macro and get
+/// registered in [`SOLUTIONS`], or in [`ALTERNATIVES`] for other implementations of the
+/// same puzzle.
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+
+    fn part(&self) -> Part;
+
+    /// Solve the puzzle, timing the parse and process stages separately.
+    fn run(&self, input: &str) -> Result<Run>;
+
+    /// Solve the puzzle without timing it, which needs no clock.
+    fn solve(&self, input: &str) -> Result<Answer> {
+        Ok(self.run(input)?.answer)
+    }
+
+    /// Like [`run`](Solution::run), with other parameters than the ones of the puzzle
+    /// text. Most puzzles have none and ignore them.
+    fn run_with(&self, input: &str, _params: &Params) -> Result<Run> {
+        self.run(input)
+    }
+
+    /// Like [`solve`](Solution::solve), with other parameters than the ones of the puzzle
+    /// text.
+    fn solve_with(&self, input: &str, _params: &Params) -> Result<Answer> {
+        self.solve(input)
+    }
+
+    /// Parse the input once, then process it `warmup` times without timing it and `runs`
+    /// times timing it.
+    fn repeat(&self, input: &str, warmup: usize, runs: usize) -> Result<Repeated>;
+
+    /// Like [`repeat`](Solution::repeat), with other parameters than the ones of the
+    /// puzzle text.
+    fn repeat_with(
+        &self,
+        input: &str,
+        _params: &Params,
+        warmup: usize,
+        runs: usize,
+    ) -> Result<Repeated> {
+        self.repeat(input, warmup, runs)
+    }
+
+    /// Name used on the command line, i.e. `day07a`.
+    fn name(&self) -> String {
+        format!("day{:02}{}", self.day(), self.part())
+    }
+
+    /// Name of an alternative implementation, `None` for the main one.
+    fn variant(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The name followed by the variant of an alternative implementation, i.e.
+    /// `day20a/chris`.
+    fn label(&self) -> String {
+        match self.variant() {
+            Some(variant) => format!("{}/{variant}", self.name()),
+            None => self.name(),
+        }
+    }
+}
+
+/// Implement [`Solution`] for a new unit struct.
+///
+/// The input is first handed to the parse function, then a mutable reference to its
+/// result is passed to the process function which produces the answer, anything
+/// convertible into an [`Answer`]. Both return a [`Result`](crate::error::Result).
+///
+/// ```ignore
+/// solution!(Day03b, 3, B, parse_input, |rucksacks| process_input_b(rucksacks, 3));
+/// ```
+///
+/// Alternative implementations are named by a variant, given after the part:
+///
+/// ```ignore
+/// solution!(Day20aChris, 20, A, "chris", Ok, |input| Ok(process_part1(input)));
+/// ```
+///
+/// The puzzles taking [parameters](crate::params::Params) say so after the part, their
+/// process function getting them as a second argument:
+///
+/// ```ignore
+/// solution!(Day07b, 7, B, params, parse_input_a, |root, params| {
+///     process_input_b(root, &params.day07)
+/// });
+/// ```
+#[macro_export]
+macro_rules! solution {
+    ($name:ident, $day:expr, $part:ident, params, $parse:expr, $process:expr) => {
+        pub struct $name;
+
+        impl $crate::solution::Solution for $name {
+            fn day(&self) -> u8 {
+                $day
+            }
+
+            fn part(&self) -> $crate::solution::Part {
+                $crate::solution::Part::$part
+            }
+
+            fn run(&self, input: &str) -> $crate::error::Result<$crate::solution::Run> {
+                self.run_with(input, &$crate::params::Params::default())
+            }
+
+            fn solve(&self, input: &str) -> $crate::error::Result<$crate::solution::Answer> {
+                self.solve_with(input, &$crate::params::Params::default())
+            }
+
+            fn repeat(
+                &self,
+                input: &str,
+                warmup: usize,
+                runs: usize,
+            ) -> $crate::error::Result<$crate::solution::Repeated> {
+                self.repeat_with(input, &$crate::params::Params::default(), warmup, runs)
+            }
+
+            fn run_with(
+                &self,
+                input: &str,
+                params: &$crate::params::Params,
+            ) -> $crate::error::Result<$crate::solution::Run> {
+                $crate::solution::parse_and_process(input, $parse, |parsed| {
+                    $crate::solution::with_params(parsed, params, $process)
+                })
+            }
+
+            fn solve_with(
+                &self,
+                input: &str,
+                params: &$crate::params::Params,
+            ) -> $crate::error::Result<$crate::solution::Answer> {
+                $crate::solution::parse_then_process(input, $parse, |parsed| {
+                    $crate::solution::with_params(parsed, params, $process)
+                })
+            }
+
+            fn repeat_with(
+                &self,
+                input: &str,
+                params: &$crate::params::Params,
+                warmup: usize,
+                runs: usize,
+            ) -> $crate::error::Result<$crate::solution::Repeated> {
+                $crate::solution::parse_once_and_process(
+                    input,
+                    $parse,
+                    |parsed| $crate::solution::with_params(parsed, params, $process),
+                    warmup,
+                    runs,
+                )
+            }
+        }
+    };
+    ($name:ident, $day:expr, $part:ident, $parse:expr, $process:expr) => {
+        pub struct $name;
+
+        impl $crate::solution::Solution for $name {
+            fn day(&self) -> u8 {
+                $day
+            }
+
+            fn part(&self) -> $crate::solution::Part {
+                $crate::solution::Part::$part
+            }
+
+            fn run(&self, input: &str) -> $crate::error::Result<$crate::solution::Run> {
+                $crate::solution::parse_and_process(input, $parse, $process)
+            }
+
+            fn solve(&self, input: &str) -> $crate::error::Result<$crate::solution::Answer> {
+                $crate::solution::parse_then_process(input, $parse, $process)
+            }
+
+            fn repeat(
+                &self,
+                input: &str,
+                warmup: usize,
+                runs: usize,
+            ) -> $crate::error::Result<$crate::solution::Repeated> {
+                $crate::solution::parse_once_and_process(input, $parse, $process, warmup, runs)
+            }
+        }
+    };
+    ($name:ident, $day:expr, $part:ident, $variant:literal, $parse:expr, $process:expr) => {
+        pub struct $name;
+
+        impl $crate::solution::Solution for $name {
+            fn day(&self) -> u8 {
+                $day
+            }
+
+            fn part(&self) -> $crate::solution::Part {
+                $crate::solution::Part::$part
+            }
+
+            fn run(&self, input: &str) -> $crate::error::Result<$crate::solution::Run> {
+                $crate::solution::parse_and_process(input, $parse, $process)
+            }
+
+            fn solve(&self, input: &str) -> $crate::error::Result<$crate::solution::Answer> {
+                $crate::solution::parse_then_process(input, $parse, $process)
+            }
+
+            fn repeat(
+                &self,
+                input: &str,
+                warmup: usize,
+                runs: usize,
+            ) -> $crate::error::Result<$crate::solution::Repeated> {
+                $crate::solution::parse_once_and_process(input, $parse, $process, warmup, runs)
+            }
+
+            fn variant(&self) -> Option<&'static str> {
+                Some($variant)
+            }
+        }
+    };
+}
+
+/// Run and time both stages of a solution.
+///
+/// Taking the stages as arguments lets the compiler infer the closure types used in the
+/// [`solution!`](crate::solution!) invocations.
+pub fn parse_and_process<'a, T, R>(
+    input: &'a str,
+    parse: impl FnOnce(&'a str) -> Result<T>,
+    process: impl FnOnce(&mut T) -> Result<R>,
+) -> Result<Run>
+where
+    R: Into<Answer>,
+{
+    let start = Instant::now();
+    let mut parsed = parse(input)?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = process(&mut parsed)?.into();
+    let solve_time = start.elapsed();
+
+    Ok(Run {
+        answer,
+        parse: parse_time,
+        solve: solve_time,
+    })
+}
+
+/// Run both stages of a solution, like [`parse_and_process`] but without timing them.
+pub fn parse_then_process<'a, T, R>(
+    input: &'a str,
+    parse: impl FnOnce(&'a str) -> Result<T>,
+    process: impl FnOnce(&mut T) -> Result<R>,
+) -> Result<Answer>
+where
+    R: Into<Answer>,
+{
+    let mut parsed = parse(input)?;
+    Ok(process(&mut parsed)?.into())
+}
+
+/// Parse the input once, then run the process stage `warmup` times without timing it and
+/// `runs` times timing it.
+///
+/// Some process functions change the parsed input, each run gets a copy of it made before
+/// starting the clock.
+pub fn parse_once_and_process<'a, T, R>(
+    input: &'a str,
+    parse: impl FnOnce(&'a str) -> Result<T>,
+    mut process: impl FnMut(&mut T) -> Result<R>,
+    warmup: usize,
+    runs: usize,
+) -> Result<Repeated>
+where
+    T: Clone,
+    R: Into<Answer>,
+{
+    let start = Instant::now();
+    let parsed = parse(input)?;
+    let parse_time = start.elapsed();
+
+    let mut answer = None;
+    let mut solve = Vec::with_capacity(runs);
+    for run in 0..warmup + runs {
+        let mut copy = parsed.clone();
+        let start = Instant::now();
+        let result = process(&mut copy)?.into();
+        if run >= warmup {
+            solve.push(start.elapsed());
+            answer = Some(result);
+        }
+    }
+
+    Ok(Repeated {
+        answer,
+        parse: parse_time,
+        solve,
+    })
+}
+
+/// Hand the parameters to the process function of a [`solution!`](crate::solution!)
+/// along with the parsed input, which lets the compiler infer the closure types.
+pub fn with_params<T, R>(
+    parsed: &mut T,
+    params: &Params,
+    process: impl FnOnce(&mut T, &Params) -> Result<R>,
+) -> Result<R> {
+    process(parsed, params)
+}
+
+/// All the solved puzzles, ordered by day and part.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01a,
+    &day01::Day01b,
+    &day02::Day02a,
+    &day02::Day02b,
+    &day03::Day03a,
+    &day03::Day03b,
+    &day04::Day04a,
+    &day04::Day04b,
+    &day05::Day05a,
+    &day05::Day05b,
+    &day06::Day06a,
+    &day06::Day06b,
+    &day07::Day07a,
+    &day07::Day07b,
+    &day08::Day08a,
+    &day08::Day08b,
+    &day09::Day09a,
+    &day10::Day10a,
+    &day10::Day10b,
+    &day11::Day11a,
+    &day12::Day12a,
+    &day12::Day12b,
+    &day13::Day13a,
+    &day13::Day13b,
+    &day14::Day14a,
+    &day14::Day14b,
+    &day15::Day15a,
+    &day17::Day17a,
+    &day18::Day18a,
+    &day20::Day20a,
+    &day20::Day20b,
+    &day21::Day21a,
+    &day22::Day22a,
+];
+
+/// Other implementations of the solved puzzles, to cross-check the main ones.
+pub static ALTERNATIVES: &[&dyn Solution] = &[&day20::Day20aChris];
+
+/// Parse a solution name like `day07a` into its day and part.
+pub fn parse_name(i: &str) -> IResult<&str, (u8, Part)> {
+    pair(preceded(tag("day"), complete::u8), Part::parse)(i)
+}
+
+/// Look up the solution for a specific day and part.
+pub fn find(day: u8, part: Part) -> Option<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|s| s.day() == day && s.part() == part)
+        .copied()
+}
+
+/// Every implementation of a puzzle, the main one first.
+pub fn implementations(day: u8, part: Part) -> Vec<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .chain(ALTERNATIVES)
+        .filter(|s| s.day() == day && s.part() == part)
+        .copied()
+        .collect()
+}
+
+/// The day and part named by a whole solution name, i.e. `day07a`.
+pub fn day_and_part(name: &str) -> Option<(u8, Part)> {
+    all_consuming(parse_name)(name)
+        .finish()
+        .ok()
+        .map(|(_, day_and_part)| day_and_part)
+}
+
+/// Look up a solution by its name, i.e. `day07a`.
+pub fn find_by_name(name: &str) -> Option<&'static dyn Solution> {
+    let (day, part) = day_and_part(name)?;
+    find(day, part)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_name() {
+        assert_eq!(parse_name("day07a"), Ok(("", (7, Part::A))));
+        assert_eq!(parse_name("day22b"), Ok(("", (22, Part::B))));
+        assert!(parse_name("day07c").is_err());
+        assert_eq!(parse_name("day07ab"), Ok(("b", (7, Part::A))));
+    }
+
+    #[test]
+    fn test_find_by_name() {
+        let solution = find_by_name("day07a").unwrap();
+        assert_eq!(solution.day(), 7);
+        assert_eq!(solution.part(), Part::A);
+        assert_eq!(solution.name(), "day07a");
+        assert!(find_by_name("day19a").is_none());
+        assert!(find_by_name("dya07a").is_none());
+        assert!(find_by_name("day07ab").is_none());
+        assert_eq!(day_and_part("day19a"), Some((19, Part::A)));
+        assert_eq!(day_and_part("dya07a"), None);
+    }
+
+    #[test]
+    fn test_implementations() {
+        let labels = implementations(20, Part::A)
+            .iter()
+            .map(|s| s.label())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, ["day20a", "day20a/chris"]);
+        assert_eq!(implementations(7, Part::A).len(), 1);
+        assert!(implementations(19, Part::A).is_empty());
+    }
+
+    #[test]
+    fn test_parse_once_and_process() {
+        let mut parses = 0;
+        let repeated = parse_once_and_process(
+            "1\n2\n3\n",
+            |i| {
+                parses += 1;
+                Ok(i.lines()
+                    .map(|l| l.parse::<u32>().unwrap())
+                    .collect::<Vec<_>>())
+            },
+            // Changing the parsed input must not affect the next runs.
+            |numbers| Ok(numbers.drain(..).sum::<u32>()),
+            2,
+            3,
+        )
+        .unwrap();
+        assert_eq!(parses, 1);
+        assert_eq!(repeated.answer, Some(Answer::Int(6)));
+        assert_eq!(repeated.solve.len(), 3);
+
+        // The sand piling up in the cave, each run must start from a fresh copy of it.
+        let repeated = find_by_name("day14a")
+            .unwrap()
+            .repeat(
+                "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n",
+                1,
+                2,
+            )
+            .unwrap();
+        assert_eq!(repeated.answer, Some(Answer::Int(24)));
+    }
+
+    #[test]
+    fn test_solutions_are_ordered_and_unique() {
+        let keys = SOLUTIONS
+            .iter()
+            .map(|s| (s.day(), s.part()))
+            .collect::<Vec<_>>();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(keys, sorted);
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    io::{self, BufRead, Write},
+    thread,
+    time::{Duration, Instant},
+};
+
+use log::warn;
+
+use crate::{
+    day05, day09, day14, day17, day22,
+    error::Result,
+    params::Params,
+    solution::{parse_then_process, Answer, Part},
+};
+
+/// The days whose simulations can be watched.
+pub const VISUALIZED_DAYS: &[u8] = &[5, 9, 14, 17, 22];
+
+/// The frame rate of the terminal playback, unless told otherwise.
+pub const DEFAULT_FPS: f64 = 30.0;
+
+/// A step of a simulation: a caption and a picture, its rows separated by newlines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub picture: String,
+}
+
+impl Frame {
+    pub fn new<C: Into<String>, P: Into<String>>(caption: C, picture: P) -> Self {
+        Frame {
+            caption: caption.into(),
+            picture: picture.into(),
+        }
+    }
+}
+
+/// Where the simulations send their frames.
+///
+/// The simulations only draw a frame when the visualizer is [enabled](Visualizer::enabled),
+/// so that solving them with a [`NullVisualizer`] is as fast as without any.
+pub trait Visualizer {
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    fn show(&mut self, frame: Frame);
+
+    /// Show the frame built by `frame`, if enabled.
+    fn draw(&mut self, frame: &dyn Fn() -> Frame) {
+        if self.enabled() {
+            self.show(frame());
+        }
+    }
+}
+
+/// Discards the frames, which are never drawn.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NullVisualizer;
+
+impl Visualizer for NullVisualizer {
+    fn enabled(&self) -> bool {
+        false
+    }
+
+    fn show(&mut self, _frame: Frame) {}
+}
+
+/// Keeps every frame.
+#[derive(Clone, Debug, Default)]
+pub struct Recorder {
+    pub frames: Vec<Frame>,
+}
+
+impl Visualizer for Recorder {
+    fn show(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+}
+
+/// How fast the frames of a terminal playback follow each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pace {
+    /// A number of frames per second, going as fast as possible when not positive.
+    Fps(f64),
+    /// Wait for Enter before each new frame.
+    Step,
+}
+
+/// Plays the frames back in a terminal, redrawing the screen with ANSI escape codes.
+///
+/// In step mode, the next frame is shown when Enter is pressed, while `c` then Enter
+/// plays the remaining ones at the default frame rate.
+pub struct AnsiVisualizer<W, R> {
+    output: W,
+    input: R,
+    pace: Pace,
+    count: usize,
+    last: Option<Instant>,
+}
+
+impl AnsiVisualizer<io::Stdout, io::StdinLock<'static>> {
+    /// Play the frames back on stdout, reading the steps from stdin.
+    pub fn terminal(pace: Pace) -> Self {
+        AnsiVisualizer::new(io::stdout(), io::stdin().lock(), pace)
+    }
+}
+
+impl<W: Write, R: BufRead> AnsiVisualizer<W, R> {
+    pub fn new(output: W, input: R, pace: Pace) -> Self {
+        AnsiVisualizer {
+            output,
+            input,
+            pace,
+            count: 0,
+            last: None,
+        }
+    }
+
+    /// Wait for the user, or for the next frame to be due.
+    fn wait(&mut self) -> io::Result<()> {
+        match self.pace {
+            Pace::Step => {
+                write!(self.output, "Enter: next frame, c: play the rest ")?;
+                self.output.flush()?;
+                let mut line = String::new();
+                // Stop stepping once there is nothing left to read.
+                if self.input.read_line(&mut line)? == 0 || line.trim() == "c" {
+                    self.pace = Pace::Fps(DEFAULT_FPS);
+                }
+            }
+            Pace::Fps(fps) if fps > 0.0 => {
+                let period = Duration::from_secs_f64(1.0 / fps);
+                if let Some(elapsed) = self.last.map(|last| last.elapsed()) {
+                    thread::sleep(period.saturating_sub(elapsed));
+                }
+            }
+            Pace::Fps(_) => {}
+        }
+        self.last = Some(Instant::now());
+        Ok(())
+    }
+
+    fn play(&mut self, frame: &Frame) -> io::Result<()> {
+        if self.count > 0 {
+            self.wait()?;
+        }
+        self.count += 1;
+        // Move home and clear the screen, then draw the frame.
+        write!(self.output, "\x1b[H\x1b[2J")?;
+        writeln!(self.output, "#{} {}", self.count, frame.caption)?;
+        writeln!(self.output, "{}", frame.picture.trim_end_matches('\n'))?;
+        self.output.flush()
+    }
+}
+
+impl<W: Write, R: BufRead> Visualizer for AnsiVisualizer<W, R> {
+    /// A broken terminal stops the playback, not the simulation.
+    fn show(&mut self, frame: Frame) {
+        if let Err(e) = self.play(&frame) {
+            warn!("Cannot show frame {}: {e}", self.count);
+        }
+    }
+}
+
+/// Solve a puzzle while sending the frames of its simulation to the visualizer, if it is
+/// one of the [visualized days](VISUALIZED_DAYS).
+pub fn visualize(
+    day: u8,
+    part: Part,
+    input: &str,
+    params: &Params,
+    visualizer: &mut dyn Visualizer,
+) -> Option<Result<Answer>> {
+    let answer = match (day, part) {
+        (5, Part::A) => parse_then_process(input, day05::parse_input_a, |(stacks, moves)| {
+            day05::process_input_a(stacks, moves, visualizer)
+        }),
+        (5, Part::B) => parse_then_process(input, day05::parse_input_a, |(stacks, moves)| {
+            day05::process_input_b(stacks, moves, visualizer)
+        }),
+        (9, Part::A) => parse_then_process(input, day09::parse_input_a, |motions| {
+            Ok(day09::process_input(motions, visualizer))
+        }),
+        (14, Part::A) => parse_then_process(input, day14::parse_input_a, |cave| {
+            day14::process_input_a(cave, visualizer)
+        }),
+        (14, Part::B) => parse_then_process(input, day14::parse_input_a, |cave| {
+            day14::process_input_b(cave, visualizer)
+        }),
+        (17, Part::A) => parse_then_process(input, day17::parse_input_a, |motions| {
+            day17::process_input_a(motions, params.day17.rocks, visualizer)
+        }),
+        (22, Part::A) => parse_then_process(input, day22::parse_input_a, |(jungle, motions)| {
+            day22::process_input_a(jungle, motions, visualizer)
+        }),
+        _ => return None,
+    };
+    Some(answer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ansi_visualizer() {
+        let mut output = Vec::new();
+        let mut visualizer = AnsiVisualizer::new(&mut output, io::empty(), Pace::Fps(0.0));
+        visualizer.draw(&|| Frame::new("Start", "#.\n.#\n"));
+        visualizer.show(Frame::new("End", ".#"));
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1b[H\x1b[2J#1 Start\n#.\n.#\n\x1b[H\x1b[2J#2 End\n.#\n"
+        );
+    }
+
+    #[test]
+    fn test_ansi_visualizer_steps() {
+        let mut output = Vec::new();
+        let mut visualizer = AnsiVisualizer::new(&mut output, "\nc\n".as_bytes(), Pace::Step);
+        for n in 0..4 {
+            visualizer.show(Frame::new(n.to_string(), "."));
+        }
+        assert_eq!(visualizer.pace, Pace::Fps(DEFAULT_FPS));
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("Enter: next frame").count(), 2);
+        assert!(output.ends_with("#4 3\n.\n"));
+    }
+
+    #[test]
+    fn test_null_visualizer() {
+        let mut visualizer = NullVisualizer;
+        visualizer.draw(&|| panic!("The frame must not be drawn"));
+        assert!(visualize(3, Part::A, "", &Params::default(), &mut visualizer).is_none());
+    }
+}
@@ -0,0 +1,343 @@
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    answers::{KnownAnswers, Verdict},
+    error::Result,
+    solution::{find, Answer, Part},
+};
+
+/// How often the watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification times of the watched files, `None` for the missing ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Self {
+        Snapshot(
+            paths
+                .iter()
+                .map(|path| {
+                    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+                    (path.clone(), modified)
+                })
+                .collect(),
+        )
+    }
+
+    /// The files created, modified or removed since the previous snapshot.
+    pub fn changes(&self, previous: &Snapshot) -> Vec<&Path> {
+        self.0
+            .iter()
+            .filter(|entry| !previous.0.contains(entry))
+            .map(|(path, _)| path.as_path())
+            .collect()
+    }
+}
+
+/// The answer of a puzzle part, or the reason it failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solved {
+    pub part: Part,
+    pub answer: Result<Answer, String>,
+}
+
+/// A line printed by `--format json`, only the fields the watch needs.
+#[derive(Deserialize)]
+struct Record {
+    answer: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+/// Read the answer out of the output of a solution run with `--format json`, `None` when
+/// it printed none.
+pub fn parse_output(part: Part, output: &str) -> Option<Solved> {
+    let line = output.lines().find(|l| l.starts_with('{'))?;
+    let answer = match serde_json::from_str::<Record>(line) {
+        Ok(Record {
+            answer: Some(serde_json::Value::String(s)),
+            ..
+        }) if s.contains('\n') => Ok(Answer::Picture(s)),
+        Ok(Record {
+            answer: Some(serde_json::Value::String(s)),
+            ..
+        }) => Ok(Answer::Text(s)),
+        Ok(Record {
+            answer: Some(serde_json::Value::Number(n)),
+            ..
+        }) => n
+            .to_string()
+            .parse()
+            .map(Answer::Int)
+            .map_err(|e| format!("Invalid answer `{n}`: {e}")),
+        Ok(Record {
+            error: Some(error), ..
+        }) => Err(error),
+        Ok(_) => Err(String::from("No answer")),
+        Err(e) => Err(format!("Invalid output `{line}`: {e}")),
+    };
+    Some(Solved { part, answer })
+}
+
+/// Render the answers of a day, with their verdict and how they changed since the
+/// previous run, if any.
+pub fn render_changes(
+    day: u8,
+    solved: &[Solved],
+    previous: Option<&[Solved]>,
+    known: &KnownAnswers,
+) -> String {
+    let mut lines = String::new();
+    for Solved { part, answer } in solved {
+        let name = format!("day{day:02}{part}");
+        let mut notes = Vec::new();
+        let (verdict, shown) = match answer {
+            Ok(answer) => {
+                let verdict = known.check(day, *part, answer);
+                if let Verdict::Fail(reason) = &verdict {
+                    notes.push(reason.clone());
+                }
+                (verdict, answer.inline())
+            }
+            Err(e) => (Verdict::Fail(e.clone()), format!("Error: {e}")),
+        };
+        if let Some(previous) = previous {
+            match previous.iter().find(|p| p.part == *part).map(|p| &p.answer) {
+                Some(before) if before == answer => notes.push(String::from("unchanged")),
+                Some(Ok(before)) => notes.push(format!("was {}", before.inline())),
+                Some(Err(_)) => notes.push(String::from("was an error")),
+                None => notes.push(String::from("new")),
+            }
+        }
+        write!(lines, "{name}  {:<8}{shown}", verdict.to_string()).unwrap();
+        if !notes.is_empty() {
+            write!(lines, " ({})", notes.join(", ")).unwrap();
+        }
+        lines.push('\n');
+    }
+    lines
+}
+
+/// Re-runs the tests of a day and solves it again each time its source, its input or the
+/// known answers change.
+///
+/// The solutions being rebuilt, they are run through cargo rather than within this
+/// process.
+#[derive(Clone, Debug)]
+pub struct Watcher {
+    day: u8,
+    input: PathBuf,
+    answers: PathBuf,
+    cargo: OsString,
+}
+
+impl Watcher {
+    pub fn new<P: AsRef<Path>, Q: AsRef<Path>>(day: u8, input: P, answers: Q) -> Self {
+        Watcher {
+            day,
+            input: input.as_ref().to_path_buf(),
+            answers: answers.as_ref().to_path_buf(),
+            // Set when running through cargo itself.
+            cargo: env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo")),
+        }
+    }
+
+    /// The source of the day, with its tests, then its input and the known answers.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        vec![
+            PathBuf::from(format!("src/day{:02}.rs", self.day)),
+            self.input.clone(),
+            self.answers.clone(),
+        ]
+    }
+
+    /// Run the tests of the day, their output going to the terminal, returning whether
+    /// they passed.
+    pub fn test(&self) -> io::Result<bool> {
+        let status = Command::new(&self.cargo)
+            .args(["test", "--quiet", "--lib", "--"])
+            .arg(format!("day{:02}::", self.day))
+            .status()?;
+        Ok(status.success())
+    }
+
+    /// Solve the parts of the day solved when the watch started.
+    pub fn solve(&self) -> io::Result<Vec<Solved>> {
+        let mut solved = Vec::new();
+        for part in [Part::A, Part::B] {
+            if find(self.day, part).is_none() {
+                continue;
+            }
+            let output = Command::new(&self.cargo)
+                .args(["run", "--quiet", "--release", "--"])
+                .arg(format!("day{:02}{part}", self.day))
+                .arg("--format")
+                .arg("json")
+                .arg("--input")
+                .arg(&self.input)
+                .stderr(Stdio::inherit())
+                .output()?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            solved.push(parse_output(part, &stdout).unwrap_or_else(|| Solved {
+                part,
+                answer: Err(format!("No answer, cargo exited with {}", output.status)),
+            }));
+        }
+        Ok(solved)
+    }
+
+    /// Watch the files until interrupted, testing and solving the day again after each
+    /// change.
+    pub fn run(&self) -> Result<()> {
+        let paths = self.paths();
+        let mut snapshot = Snapshot::default();
+        let mut previous: Option<Vec<Solved>> = None;
+        loop {
+            let current = Snapshot::take(&paths);
+            let changes = current.changes(&snapshot);
+            if changes.is_empty() {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            if previous.is_some() {
+                for path in changes {
+                    println!("Changed: {}", path.display());
+                }
+            }
+            snapshot = current;
+
+            if self.test()? {
+                println!("The tests of day {} pass", self.day);
+            } else {
+                println!("The tests of day {} fail", self.day);
+            }
+            let solved = self.solve()?;
+            let known = KnownAnswers::load(&self.answers)?;
+            print!(
+                "{}",
+                render_changes(self.day, &solved, previous.as_deref(), &known)
+            );
+            println!("Watching {} for changes", self.day_files());
+            previous = Some(solved);
+        }
+    }
+
+    fn day_files(&self) -> String {
+        self.paths()
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (input, missing) = (dir.join("day14.txt"), dir.join("answers.txt"));
+        fs::write(&input, "498,4 -> 498,6").unwrap();
+        let paths = [input.clone(), missing.clone()];
+
+        let first = Snapshot::take(&paths);
+        assert_eq!(first.changes(&Snapshot::default()), [&input, &missing]);
+        assert!(Snapshot::take(&paths).changes(&first).is_empty());
+
+        let file = fs::File::options().write(true).open(&input).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(1))
+            .unwrap();
+        let second = Snapshot::take(&paths);
+        assert_eq!(second.changes(&first), [&input]);
+
+        fs::write(&missing, "day14a = 24").unwrap();
+        assert_eq!(Snapshot::take(&paths).changes(&second), [&missing]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_output() {
+        assert_eq!(
+            parse_output(
+                Part::A,
+                "{\"name\":\"day14a\",\"answer\":24,\"error\":null}\n"
+            ),
+            Some(Solved {
+                part: Part::A,
+                answer: Ok(Answer::Int(24))
+            })
+        );
+        assert_eq!(
+            parse_output(Part::B, "{\"answer\":\"#.\\n.#\",\"error\":null}"),
+            Some(Solved {
+                part: Part::B,
+                answer: Ok(Answer::Picture(String::from("#.\n.#")))
+            })
+        );
+        assert_eq!(
+            parse_output(Part::A, "{\"answer\":null,\"error\":\"Panicked\"}"),
+            Some(Solved {
+                part: Part::A,
+                answer: Err(String::from("Panicked"))
+            })
+        );
+        assert_eq!(parse_output(Part::B, ""), None);
+    }
+
+    #[test]
+    fn test_render_changes() {
+        let known = "day14a = 24\nday14b != 0".parse::<KnownAnswers>().unwrap();
+        let first = [
+            Solved {
+                part: Part::A,
+                answer: Ok(Answer::Int(24)),
+            },
+            Solved {
+                part: Part::B,
+                answer: Ok(Answer::Int(0)),
+            },
+        ];
+        assert_eq!(
+            render_changes(14, &first, None, &known),
+            "day14a  PASS    24\nday14b  FAIL    0 (expected != 0)\n"
+        );
+
+        let second = [
+            Solved {
+                part: Part::A,
+                answer: Ok(Answer::Int(24)),
+            },
+            Solved {
+                part: Part::B,
+                answer: Err(String::from("Panicked")),
+            },
+        ];
+        assert_eq!(
+            render_changes(14, &second, Some(&first), &known),
+            "day14a  PASS    24 (unchanged)\nday14b  FAIL    Error: Panicked (was 0)\n"
+        );
+        assert_eq!(
+            render_changes(14, &first, Some(&second), &known),
+            "day14a  PASS    24 (unchanged)\nday14b  FAIL    0 (expected != 0, was an error)\n"
+        );
+        assert_eq!(
+            render_changes(14, &first[1..], Some(&[]), &known),
+            "day14b  FAIL    0 (expected != 0, new)\n"
+        );
+    }
+}